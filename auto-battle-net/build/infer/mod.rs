@@ -138,9 +138,40 @@ fn fields(path: &Vec<String>, map: &JsonMap) -> Vec<Field> {
         }
     }
 
+    // the sampled encounter has no abilities, but boss sections link the spell they describe
+    if is_journal_encounter_section(path, map) && !map.contains_key("spell") {
+        fields.push(Field {
+            name: "spell".to_string(),
+            ty: FieldType::Optional(Box::new(RefCell::new(FieldType::Struct(Struct {
+                name: "Spell".to_string(),
+                path: path.clone(),
+                fields: vec![
+                    Field {
+                        name: "id".to_string(),
+                        ty: FieldType::Primitive("i64"),
+                    },
+                    Field {
+                        name: "name".to_string(),
+                        ty: FieldType::Primitive("crate::LocalizedString"),
+                    },
+                ],
+                serialize: false,
+                deserialize: true,
+            })))),
+        });
+    }
+
     fields
 }
 
+fn is_journal_encounter_section(path: &[String], map: &JsonMap) -> bool {
+    path.len() >= 3
+        && path[0] == "game_data"
+        && path[1] == "journal"
+        && path[2] == "journal_encounter"
+        && map.contains_key("title")
+}
+
 fn object(path: &Vec<String>, key: &str, value: &JsonValue) -> Option<FieldType> {
     let optionals = vec![
         (
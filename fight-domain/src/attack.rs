@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use i18n::LocalizedString;
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::attack_timer::AttackTimer;
-use crate::LookupKey;
+use crate::{Identifier, LookupKey};
 use crate::serde_not_nan::{deserialize_not_nan, serialize_not_nan};
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -16,6 +17,10 @@ impl AttackUuid {
     pub const fn new(uuid: Uuid) -> AttackUuid {
         Self(uuid)
     }
+
+    pub fn random() -> AttackUuid {
+        Self(Uuid::new_v4())
+    }
 }
 
 impl Display for AttackUuid {
@@ -27,7 +32,10 @@ impl Display for AttackUuid {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Attack {
     pub uuid: AttackUuid,
-    pub name: String,
+    pub name: LocalizedString,
+    /// Boss ability, used for its icon and in the exported note.
+    #[serde(default)]
+    pub identifier: Option<Identifier>,
     #[serde(
         default = "default_power",
         serialize_with = "serialize_not_nan",
@@ -58,26 +66,36 @@ pub enum AttackType {
     Generic,
 }
 
+// TODO: this will change and be "much more clear"
+//       in the next version of the spreadsheet
+//       spoiler: it wasn't
+const ATTACK_TYPE_COLORS: [(&str, AttackType); 8] = [
+    ("Red", AttackType::RaidDamage),           // Raid damage
+    ("Orange", AttackType::RaidDamageStacked), // Raid damage, stacked raid
+    ("Yellow", AttackType::RotDamage),         // Rot damage
+    ("Green", AttackType::Movement),           // Movement
+    ("Blue", AttackType::Dispels),             // Dispels
+    ("Purple", AttackType::Debuffs),           // Debuff related
+    ("Pink", AttackType::Adds),                // Adds?
+    ("Light Grey", AttackType::Generic),       // Generic
+];
+
+impl AttackType {
+    /// Colour of the attack type in the healing-cooldown spreadsheet.
+    pub fn color_name(&self) -> &'static str {
+        ATTACK_TYPE_COLORS
+            .iter()
+            .find(|(_, attack_type)| attack_type == self)
+            .map(|(color, _)| *color)
+            .unwrap()
+    }
+}
+
 impl TryFrom<&str> for AttackType {
     type Error = ();
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        use AttackType::*;
-        let power_mapping: HashMap<&str, AttackType> = [
-            // TODO: this will change and be "much more clear"
-            //       in the next version of the spreadsheet
-            //       spoiler: it wasn't
-            ("Red", RaidDamage),           // Raid damage
-            ("Orange", RaidDamageStacked), // Raid damage, stacked raid
-            ("Yellow", RotDamage),         // Rot damage
-            ("Green", Movement),           // Movement
-            ("Blue", Dispels),             // Dispels
-            ("Purple", Debuffs),           // Debuff related
-            ("Pink", Adds),                // Adds?
-            ("Light Grey", Generic),       // Generic
-        ]
-        .into_iter()
-        .collect();
+        let power_mapping: HashMap<&str, AttackType> = ATTACK_TYPE_COLORS.into_iter().collect();
 
         power_mapping.get(value).copied().ok_or(())
     }
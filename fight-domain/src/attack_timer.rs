@@ -1,6 +1,7 @@
 use crate::TimeStep;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Default, Hash)]
 pub struct AttackTimer {
@@ -47,3 +48,17 @@ impl Display for CleuEventType {
         )
     }
 }
+
+impl FromStr for CleuEventType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SCC" => Ok(CleuEventType::SpellCastSuccess),
+            "SCS" => Ok(CleuEventType::SpellCastStart),
+            "SAA" => Ok(CleuEventType::SpellAuraApplied),
+            "SAR" => Ok(CleuEventType::SpellAuraRemoved),
+            _ => Err(()),
+        }
+    }
+}
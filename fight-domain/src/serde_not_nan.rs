@@ -15,3 +15,22 @@ where
     let num = f64::deserialize(deserializer)?;
     NotNan::new(num).map_err(serde::de::Error::custom)
 }
+
+pub fn serialize_optional_not_nan<S>(value: &Option<NotNan<f64>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.serialize_some(&value.into_inner()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize_optional_not_nan<'de, D>(deserializer: D) -> Result<Option<NotNan<f64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<f64>::deserialize(deserializer)?
+        .map(|num| NotNan::new(num).map_err(serde::de::Error::custom))
+        .transpose()
+}
@@ -1,12 +1,16 @@
 use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 
+use i18n::LocalizedString;
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{Identifier, LookupKey, TimeStep};
-use crate::serde_not_nan::{deserialize_not_nan, serialize_not_nan};
+use crate::serde_not_nan::{
+    deserialize_not_nan, deserialize_optional_not_nan, serialize_not_nan,
+    serialize_optional_not_nan,
+};
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct SpellUuid(Uuid);
@@ -15,6 +19,10 @@ impl SpellUuid {
     pub const fn new(uuid: Uuid) -> SpellUuid {
         Self(uuid)
     }
+
+    pub fn random() -> SpellUuid {
+        Self(Uuid::new_v4())
+    }
 }
 
 impl Display for SpellUuid {
@@ -26,8 +34,7 @@ impl Display for SpellUuid {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Spell {
     pub uuid: SpellUuid,
-    // TODO: name should be a LocalizedString
-    pub name: String,
+    pub name: LocalizedString,
     pub icon_text: Option<String>,
     #[serde(
         default = "default_power",
@@ -54,6 +61,58 @@ impl LookupKey for Spell {
     }
 }
 
+/// Replacement values for the fields of a built-in spell, `None` keeps the built-in value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpellOverride {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub identifier: Option<Identifier>,
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_not_nan",
+        deserialize_with = "deserialize_optional_not_nan"
+    )]
+    pub power: Option<NotNan<f64>>,
+    #[serde(default)]
+    pub cooldown: Option<TimeStep>,
+    #[serde(default)]
+    pub cast_time: Option<TimeStep>,
+    #[serde(default)]
+    pub charges: Option<usize>,
+    #[serde(default)]
+    pub exclusive_with: Option<BTreeSet<Identifier>>,
+}
+
+impl SpellOverride {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn apply(&self, spell: &Spell) -> Spell {
+        Spell {
+            name: self
+                .name
+                .as_deref()
+                .map(LocalizedString::constant)
+                .unwrap_or_else(|| spell.name.clone()),
+            identifier: self
+                .identifier
+                .clone()
+                .unwrap_or_else(|| spell.identifier.clone()),
+            power: self.power.unwrap_or(spell.power),
+            cooldown: self.cooldown.unwrap_or(spell.cooldown),
+            cast_time: self.cast_time.unwrap_or(spell.cast_time),
+            charges: self.charges.unwrap_or(spell.charges),
+            exclusive_with: self
+                .exclusive_with
+                .clone()
+                .unwrap_or_else(|| spell.exclusive_with.clone()),
+            ..spell.clone()
+        }
+    }
+}
+
 fn default_power() -> NotNan<f64> {
     NotNan::new(1.0).unwrap()
 }
@@ -1,13 +1,13 @@
 use crate::locale::Locale;
 use serde_lite_derive::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use strum::IntoEnumIterator;
 
-#[derive(Serialize, Deserialize, serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct LocalizedString(Arc<HashMap<Locale, String>>);
 
 impl Ord for LocalizedString {
@@ -75,6 +75,45 @@ impl LocalizedString {
             text, text, text, text, text, text, text, text, text, text, text, text,
         )
     }
+
+    pub fn map(&self, f: impl Fn(&str) -> String) -> Self {
+        Self(Arc::new(
+            self.0
+                .iter()
+                .map(|(locale, text)| (*locale, f(text)))
+                .collect(),
+        ))
+    }
+}
+
+// a string that is the same in every locale is written once, the others sorted by locale, so
+// the same string always serializes the same
+impl serde::Serialize for LocalizedString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let text = self.get(Locale::EnglishUnitedStates);
+        if self.0.values().all(|other| other == text) {
+            serializer.serialize_newtype_struct("LocalizedString", text)
+        } else {
+            let sorted = self.0.iter().collect::<BTreeMap<_, _>>();
+            serializer.serialize_newtype_struct("LocalizedString", &sorted)
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SerializedLocalizedString {
+    Constant(String),
+    Localized(HashMap<Locale, String>),
+}
+
+impl<'de> serde::Deserialize<'de> for LocalizedString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SerializedLocalizedString::deserialize(deserializer)? {
+            SerializedLocalizedString::Constant(text) => Self::constant(&text),
+            SerializedLocalizedString::Localized(texts) => Self(Arc::new(texts)),
+        })
+    }
 }
 
 impl Debug for LocalizedString {
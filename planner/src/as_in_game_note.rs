@@ -26,3 +26,6 @@ impl AsInGameNote for Identifier {
         }
     }
 }
+
+/// MRT tag of reminders for the whole raid, general assignments are exported behind it.
+pub const EVERYONE_TAG: &str = "{everyone}";
@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use itertools::Itertools;
+use ordered_float::NotNan;
+
+use fight_domain::AttackType;
+
+use crate::combat_log::{CombatLogDamage, CombatLogEncounter};
+
+/// Hits of a spell further apart than this are separate occurrences, periodic ticks are closer.
+const OCCURRENCE_GAP: Duration = Duration::from_secs(3);
+/// Periodic damage lasting at least this long wears the raid down instead of hitting it once.
+const ROT_DURATION: Duration = Duration::from_secs(6);
+
+/// Damage a boss ability did to the raid in the pulls of an encounter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackDamage {
+    pub spell_id: u32,
+    pub name: String,
+    pub occurrences: usize,
+    /// Average raid-wide damage taken per occurrence.
+    pub damage: u64,
+    /// Average players hit per occurrence.
+    pub targets: f64,
+    /// Damage per occurrence relative to the average of all abilities of the encounter.
+    pub power: NotNan<f64>,
+    pub r#type: AttackType,
+}
+
+#[derive(Debug, Default)]
+struct Occurrence {
+    start: Duration,
+    end: Duration,
+    damage: u64,
+    periodic_damage: u64,
+    targets: HashSet<String>,
+}
+
+/// Damage taken by players from hostile units in the pulls of an encounter, grouped by ability
+/// and sorted by damage per occurrence.
+pub fn damage_taken(encounters: &[CombatLogEncounter], encounter_id: i64) -> Vec<AttackDamage> {
+    let mut occurrences = HashMap::<u32, Vec<Occurrence>>::new();
+    let mut names = HashMap::new();
+    let mut raid_sizes = vec![];
+    for encounter in encounters
+        .iter()
+        .filter(|encounter| encounter.encounter_id == encounter_id)
+    {
+        raid_sizes.push(raid_size(encounter));
+        let hits = encounter
+            .damage
+            .iter()
+            .filter(|damage| !damage.source.is_player() && damage.source.is_hostile())
+            .into_group_map_by(|damage| damage.spell_id);
+        for (spell_id, hits) in hits {
+            names
+                .entry(spell_id)
+                .or_insert_with(|| hits[0].spell_name.clone());
+            occurrences
+                .entry(spell_id)
+                .or_default()
+                .extend(group_occurrences(&hits));
+        }
+    }
+    let raid_size = raid_sizes.iter().sum::<usize>() as f64 / raid_sizes.len().max(1) as f64;
+
+    let damages = occurrences
+        .into_iter()
+        .map(|(spell_id, occurrences)| {
+            let count = occurrences.len() as f64;
+            let damage = occurrences.iter().map(|o| o.damage).sum::<u64>() as f64 / count;
+            let periodic =
+                occurrences.iter().map(|o| o.periodic_damage).sum::<u64>() as f64 / count;
+            let targets = occurrences.iter().map(|o| o.targets.len()).sum::<usize>() as f64 / count;
+            let duration = occurrences
+                .iter()
+                .map(|o| o.end - o.start)
+                .sum::<Duration>()
+                .div_f64(count);
+
+            let r#type = if periodic * 2.0 >= damage && duration >= ROT_DURATION {
+                AttackType::RotDamage
+            } else if targets * 2.0 >= raid_size {
+                AttackType::RaidDamage
+            } else {
+                AttackType::Debuffs
+            };
+            (spell_id, occurrences.len(), damage, targets, r#type)
+        })
+        .collect::<Vec<_>>();
+
+    let average =
+        damages.iter().map(|(_, _, damage, ..)| damage).sum::<f64>() / damages.len().max(1) as f64;
+    damages
+        .into_iter()
+        .map(
+            |(spell_id, occurrences, damage, targets, r#type)| AttackDamage {
+                spell_id,
+                name: names[&spell_id].clone(),
+                occurrences,
+                damage: damage.round() as u64,
+                targets,
+                power: NotNan::new(if average > 0.0 { damage / average } else { 0.0 }).unwrap(),
+                r#type,
+            },
+        )
+        .sorted_by_key(|damage| (std::cmp::Reverse(damage.damage), damage.spell_id))
+        .collect()
+}
+
+fn group_occurrences(hits: &[&CombatLogDamage]) -> Vec<Occurrence> {
+    let mut occurrences = Vec::<Occurrence>::new();
+    for hit in hits {
+        let occurrence = match occurrences.last_mut() {
+            Some(occurrence) if hit.time.saturating_sub(occurrence.end) <= OCCURRENCE_GAP => {
+                occurrence
+            }
+            _ => {
+                occurrences.push(Occurrence {
+                    start: hit.time,
+                    ..Default::default()
+                });
+                occurrences.last_mut().unwrap()
+            }
+        };
+        occurrence.end = hit.time;
+        occurrence.damage += hit.amount;
+        if hit.periodic {
+            occurrence.periodic_damage += hit.amount;
+        }
+        occurrence.targets.insert(hit.target.guid.clone());
+    }
+    occurrences
+}
+
+/// Players seen in the pull, casting or taking damage.
+fn raid_size(encounter: &CombatLogEncounter) -> usize {
+    encounter
+        .events
+        .iter()
+        .map(|event| &event.source)
+        .chain(encounter.damage.iter().map(|damage| &damage.target))
+        .filter(|unit| unit.is_player())
+        .map(|unit| &unit.guid)
+        .unique()
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::combat_log::parse_combat_log;
+
+    use super::*;
+
+    fn hit(time: &str, event: &str, player: u32, spell: &str, amount: u64) -> String {
+        format!(
+            "4/19 20:{time}.000  {event},Creature-0-1,\"Kazzara, the Hellforged\",0x10a48,0x0,\
+             Player-1-{player},\"Player{player}-Test-EU\",0x514,0x0,{spell},0x1,\
+             Player-1-{player},0000000000000000,1,1,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,70,\
+             {amount},{amount},-1,1,0,0,0,nil,nil,nil\n"
+        )
+    }
+
+    #[test]
+    fn groups_damage_per_occurrence() {
+        let mut log =
+            "4/19 20:00:00.000  ENCOUNTER_START,2688,\"Kazzara, the Hellforged\",16,20,2569\n"
+                .to_string();
+        for player in 1..=4 {
+            log += &hit("00:10", "SPELL_DAMAGE", player, "401319,\"Carnage\"", 300);
+        }
+        log += &hit("00:20", "SPELL_DAMAGE", 1, "407196,\"Rifts\"", 200);
+        for second in 0..8 {
+            let time = format!("00:{}", 30 + second);
+            log += &hit(&time, "SPELL_PERIODIC_DAMAGE", 2, "400430,\"Burn\"", 25);
+        }
+        for player in 1..=4 {
+            log += &hit("00:40", "SPELL_DAMAGE", player, "401319,\"Carnage\"", 100);
+        }
+        log += "4/19 20:01:00.000  ENCOUNTER_END,2688,\"Kazzara, the Hellforged\",16,20,0,60000\n";
+
+        let damage = damage_taken(&parse_combat_log(&log), 2688);
+
+        let summary = damage
+            .iter()
+            .map(|damage| {
+                (
+                    damage.spell_id,
+                    damage.occurrences,
+                    damage.damage,
+                    damage.r#type,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (401319, 2, 800, AttackType::RaidDamage),
+                (400430, 1, 200, AttackType::RotDamage),
+                (407196, 1, 200, AttackType::Debuffs),
+            ]
+        );
+        assert_eq!(damage[0].targets, 4.0);
+        assert_eq!(damage[0].power, NotNan::new(2.0).unwrap());
+    }
+}
@@ -0,0 +1,341 @@
+use std::time::Duration;
+
+use fight_domain::{AttackTimer, CleuEventType, TimeStep};
+
+pub use damage::*;
+pub use timeline::*;
+pub use verification::*;
+pub use warcraft_logs::*;
+
+mod damage;
+mod timeline;
+mod verification;
+mod warcraft_logs;
+
+const COMBATLOG_OBJECT_REACTION_HOSTILE: u32 = 0x40;
+// amount, unmitigated amount, overkill, school, resisted, blocked, absorbed
+const ABSORBED_OFFSET: usize = 6;
+
+/// Pull of an encounter, from its `ENCOUNTER_START` to its `ENCOUNTER_END` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatLogEncounter {
+    pub encounter_id: i64,
+    pub name: String,
+    pub difficulty_id: u32,
+    /// `None` if the log ends before the encounter does.
+    pub kill: Option<bool>,
+    pub duration: Duration,
+    pub events: Vec<CombatLogEvent>,
+    /// Damage taken by players.
+    pub damage: Vec<CombatLogDamage>,
+}
+
+/// Spell event of the advanced combat log, timed from the pull.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatLogEvent {
+    pub time: Duration,
+    pub r#type: CleuEventType,
+    pub source: CombatLogUnit,
+    pub target: CombatLogUnit,
+    pub spell_id: u32,
+    pub spell_name: String,
+}
+
+/// `SPELL_DAMAGE` or `SPELL_PERIODIC_DAMAGE` line, timed from the pull.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatLogDamage {
+    pub time: Duration,
+    pub source: CombatLogUnit,
+    pub target: CombatLogUnit,
+    pub spell_id: u32,
+    pub spell_name: String,
+    /// Damage done including the part absorbed by shields.
+    pub amount: u64,
+    pub periodic: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CombatLogUnit {
+    pub guid: String,
+    /// `Name-Realm-Region` for players.
+    pub name: String,
+    pub flags: u32,
+}
+
+impl CombatLogUnit {
+    pub fn is_player(&self) -> bool {
+        self.guid.starts_with("Player-")
+    }
+
+    pub fn is_hostile(&self) -> bool {
+        self.flags & COMBATLOG_OBJECT_REACTION_HOSTILE != 0
+    }
+}
+
+impl CombatLogEncounter {
+    /// When the attack happened in this pull, `None` if the pull ended before it or its trigger
+    /// never happened.
+    pub fn attack_time(&self, timer: &AttackTimer) -> Option<Duration> {
+        let time = match &timer.dynamic_trigger_cleu_event {
+            Some(trigger) => {
+                let counter = trigger.counter.max(1) as usize;
+                let trigger = self
+                    .events
+                    .iter()
+                    .filter(|event| {
+                        event.r#type == trigger.r#type && u64::from(event.spell_id) == trigger.event
+                    })
+                    .nth(counter - 1)?;
+                trigger.time + Duration::from(timer.dynamic_timer.unwrap_or(TimeStep::zero()))
+            }
+            None => Duration::from(timer.static_timer()),
+        };
+        (time <= self.duration).then_some(time)
+    }
+}
+
+/// Encounters of an advanced combat log, lines outside of encounters are skipped.
+pub fn parse_combat_log(log: &str) -> Vec<CombatLogEncounter> {
+    let mut encounters = vec![];
+    let mut current = None::<(Duration, CombatLogEncounter)>;
+    let mut clock = LogClock::default();
+    // without advanced logging the damage fields come right after the spell
+    let mut advanced = true;
+
+    for line in log.lines() {
+        let Some((timestamp, fields)) = line.split_once("  ") else {
+            continue;
+        };
+        let Some(time) = clock.parse(timestamp) else {
+            continue;
+        };
+        let fields = split_fields(fields);
+        match fields[0].as_str() {
+            "COMBAT_LOG_VERSION" => {
+                advanced = fields.get(3).is_some_and(|enabled| enabled == "1");
+            }
+            "ENCOUNTER_START" if fields.len() >= 4 => {
+                // an encounter without an end was cut off
+                encounters.extend(current.take().map(|(_, encounter)| encounter));
+                current = Some((
+                    time,
+                    CombatLogEncounter {
+                        encounter_id: fields[1].parse().unwrap_or_default(),
+                        name: fields[2].clone(),
+                        difficulty_id: fields[3].parse().unwrap_or_default(),
+                        kill: None,
+                        duration: Duration::ZERO,
+                        events: vec![],
+                        damage: vec![],
+                    },
+                ));
+            }
+            "ENCOUNTER_END" if fields.len() >= 6 => {
+                if let Some((start, mut encounter)) = current.take() {
+                    encounter.kill = Some(fields[5] == "1");
+                    encounter.duration = time.saturating_sub(start);
+                    encounters.push(encounter);
+                }
+            }
+            event => {
+                let Some((start, encounter)) = &mut current else {
+                    continue;
+                };
+                if fields.len() < 11 {
+                    continue;
+                }
+                let time = time.saturating_sub(*start);
+                if let Some(r#type) = cleu_event_type(event) {
+                    encounter.duration = time;
+                    encounter.events.push(CombatLogEvent {
+                        time,
+                        r#type,
+                        source: unit(&fields[1..5]),
+                        target: unit(&fields[5..9]),
+                        spell_id: fields[9].parse().unwrap_or_default(),
+                        spell_name: fields[10].clone(),
+                    });
+                } else if let Some(periodic) = damage_event(event) {
+                    let target = unit(&fields[5..9]);
+                    // damage done to the boss is most of the log and not needed
+                    if !target.is_player() {
+                        continue;
+                    }
+                    encounter.duration = time;
+                    let amount_field = if advanced { 29 } else { 12 };
+                    let amount = |index: usize| {
+                        fields
+                            .get(index)
+                            .and_then(|amount| amount.parse::<u64>().ok())
+                            .unwrap_or_default()
+                    };
+                    encounter.damage.push(CombatLogDamage {
+                        time,
+                        source: unit(&fields[1..5]),
+                        target,
+                        spell_id: fields[9].parse().unwrap_or_default(),
+                        spell_name: fields[10].clone(),
+                        amount: amount(amount_field) + amount(amount_field + ABSORBED_OFFSET),
+                        periodic,
+                    });
+                }
+            }
+        }
+    }
+
+    encounters.extend(current.map(|(_, encounter)| encounter));
+    encounters
+}
+
+fn cleu_event_type(event: &str) -> Option<CleuEventType> {
+    match event {
+        "SPELL_CAST_SUCCESS" => Some(CleuEventType::SpellCastSuccess),
+        "SPELL_CAST_START" => Some(CleuEventType::SpellCastStart),
+        "SPELL_AURA_APPLIED" => Some(CleuEventType::SpellAuraApplied),
+        "SPELL_AURA_REMOVED" => Some(CleuEventType::SpellAuraRemoved),
+        _ => None,
+    }
+}
+
+/// Whether the damage event is periodic.
+fn damage_event(event: &str) -> Option<bool> {
+    match event {
+        "SPELL_DAMAGE" => Some(false),
+        "SPELL_PERIODIC_DAMAGE" => Some(true),
+        _ => None,
+    }
+}
+
+fn unit(fields: &[String]) -> CombatLogUnit {
+    let flags = fields[2].trim_start_matches("0x");
+    CombatLogUnit {
+        guid: fields[0].clone(),
+        name: fields[1].clone(),
+        flags: u32::from_str_radix(flags, 16).unwrap_or_default(),
+    }
+}
+
+/// Splits at commas outside of quotes, dropping the quotes.
+fn split_fields(fields: &str) -> Vec<String> {
+    let mut out = vec![String::new()];
+    let mut quoted = false;
+    for c in fields.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => out.push(String::new()),
+            c => out.last_mut().unwrap().push(c),
+        }
+    }
+    out
+}
+
+/// Time of day of the log lines, counting on past midnight.
+#[derive(Debug, Default)]
+struct LogClock {
+    previous: Duration,
+    days: u32,
+}
+
+impl LogClock {
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Reads `4/19 20:31:45.123` and `4/19/2024 20:31:45.1234-4`, ignoring the date and the
+    /// time zone.
+    fn parse(&mut self, timestamp: &str) -> Option<Duration> {
+        let (_, time) = timestamp.split_once(' ')?;
+        let time = time.split(['-', '+']).next()?;
+        let (seconds, fraction) = time.split_once('.').unwrap_or((time, "0"));
+        let mut parts = seconds.split(':').map(|part| part.parse::<u64>().ok());
+        let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+        let millis = format!("{fraction:0<3}")[..3].parse::<u64>().ok()?;
+
+        let time_of_day =
+            Duration::from_millis(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis);
+        let mut time = time_of_day + Self::DAY * self.days;
+        if time + Self::DAY / 2 < self.previous {
+            self.days += 1;
+            time += Self::DAY;
+        }
+        self.previous = time;
+        Some(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::{CleuEvent, FromMinutesSeconds};
+
+    use super::*;
+
+    pub(crate) const LOG: &str = r#"4/19 20:31:40.000  COMBAT_LOG_VERSION,20,ADVANCED_LOG_ENABLED,1,BUILD_VERSION,10.1.0,PROJECT_ID,1
+4/19 20:31:45.123  ENCOUNTER_START,2688,"Kazzara, the Hellforged",16,20,2569
+4/19 20:31:49.000  SPELL_CAST_SUCCESS,Player-1234-00000001,"Test-Draenor-EU",0x514,0x0,0000000000000000,nil,0x80000000,0x80000000,740,"Tranquility",0x8,Player-1234-00000001,0000000000000000,100,100,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,420
+4/19 20:32:05.123  SPELL_CAST_START,Creature-0-1-2569-1-201261-0000000001,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,401316,"Hellsteel Carnage",0x1
+4/19 20:32:05.500  SPELL_DAMAGE,Creature-0-1-2569-1-201261-0000000001,"Kazzara, the Hellforged",0x10a48,0x0,Player-1234-00000001,"Test-Draenor-EU",0x514,0x0,401319,"Hellsteel Carnage",0x1,Player-1234-00000001,0000000000000000,500000,600000,0,0,5000,0,1,0,0,0,1.00,2.00,2166,3.14,420,90000,120000,-1,1,0,0,10000,nil,nil,nil
+4/19 20:32:05.600  SPELL_DAMAGE,Player-1234-00000001,"Test-Draenor-EU",0x514,0x0,Creature-0-1-2569-1-201261-0000000001,"Kazzara, the Hellforged",0x10a48,0x0,8921,"Moonfire",0x40,Creature-0-1-2569-1-201261-0000000001,0000000000000000,99,100,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,72,20000,20000,-1,64,0,0,0,nil,nil,nil
+4/19 20:32:15.123  SPELL_CAST_START,Creature-0-1-2569-1-201261-0000000001,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,401316,"Hellsteel Carnage",0x1
+4/19 20:33:45.123  ENCOUNTER_END,2688,"Kazzara, the Hellforged",16,20,0,120000
+"#;
+
+    #[test]
+    fn parses_encounters() {
+        let encounters = parse_combat_log(LOG);
+
+        assert_eq!(encounters.len(), 1);
+        let encounter = &encounters[0];
+        assert_eq!(encounter.encounter_id, 2688);
+        assert_eq!(encounter.name, "Kazzara, the Hellforged");
+        assert_eq!(encounter.difficulty_id, 16);
+        assert_eq!(encounter.kill, Some(false));
+        assert_eq!(encounter.duration, Duration::from_secs(120));
+        assert_eq!(encounter.events.len(), 3);
+        assert_eq!(encounter.events[0].time, Duration::from_millis(3877));
+        assert_eq!(encounter.events[0].source.name, "Test-Draenor-EU");
+        assert!(encounter.events[0].source.is_player());
+        assert_eq!(encounter.events[0].spell_id, 740);
+        assert_eq!(encounter.events[1].r#type, CleuEventType::SpellCastStart);
+        assert_eq!(encounter.events[1].source.flags, 0x10a48);
+        assert_eq!(encounter.damage.len(), 1);
+        assert_eq!(encounter.damage[0].spell_id, 401319);
+        assert_eq!(encounter.damage[0].amount, 100000);
+        assert!(!encounter.damage[0].periodic);
+    }
+
+    #[test]
+    fn times_attacks_from_their_trigger() {
+        let encounter = &parse_combat_log(LOG)[0];
+        let triggered = AttackTimer {
+            dynamic_timer: Some(TimeStep::mm_ss(0, 5)),
+            dynamic_trigger_cleu_event: Some(CleuEvent {
+                r#type: CleuEventType::SpellCastStart,
+                event: 401316,
+                counter: 2,
+            }),
+            ..Default::default()
+        };
+        let too_late = AttackTimer {
+            dynamic_timer: Some(TimeStep::mm_ss(3, 0)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encounter.attack_time(&triggered),
+            Some(Duration::from_secs(35))
+        );
+        assert_eq!(encounter.attack_time(&too_late), None);
+    }
+
+    #[test]
+    fn parses_timestamps_past_midnight() {
+        let mut clock = LogClock::default();
+
+        assert_eq!(
+            clock.parse("4/19/2024 23:59:59.5000-4"),
+            Some(Duration::from_millis(86_399_500))
+        );
+        assert_eq!(
+            clock.parse("4/20/2024 00:00:01.0000-4"),
+            Some(Duration::from_millis(86_401_000))
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use itertools::Itertools;
+use ordered_float::NotNan;
+
+use fight_domain::{
+    Attack, AttackTimer, AttackType, AttackUuid, CleuEvent, CleuEventType, Identifier, Lookup,
+    TimeStep,
+};
+use i18n::LocalizedString;
+
+use crate::combat_log::{AttackDamage, CombatLogEncounter, CombatLogEvent};
+
+/// Auras applied to many players at once are one attack, not one per player.
+const BURST: Duration = Duration::from_secs(2);
+// cast starts warn first, so they are preferred when a spell shows up with several events
+const PREFERRED_TYPES: [CleuEventType; 3] = [
+    CleuEventType::SpellCastStart,
+    CleuEventType::SpellCastSuccess,
+    CleuEventType::SpellAuraApplied,
+];
+
+/// Boss attack averaged over the pulls it happened in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedAttack {
+    pub spell_id: u32,
+    pub name: String,
+    pub timer: AttackTimer,
+    pub pulls: usize,
+}
+
+impl ExtractedAttack {
+    /// Power and type come from the damage the spell did in the logs, if it did any.
+    pub fn to_attack(&self, damage: &[AttackDamage]) -> Attack {
+        let damage = damage
+            .iter()
+            .find(|damage| damage.spell_id == self.spell_id);
+        Attack {
+            uuid: AttackUuid::random(),
+            name: LocalizedString::constant(&self.name),
+            identifier: Some(Identifier::Spell(self.spell_id)),
+            power: damage.map_or(NotNan::new(1.0).unwrap(), |damage| damage.power),
+            r#type: damage.map_or(AttackType::RaidDamage, |damage| damage.r#type),
+            timer: self.timer.clone(),
+        }
+    }
+}
+
+/// Casts and auras of hostile units in the pulls of an encounter, timed from the pull or, once
+/// one of the phase spells was cast, from the latest phase spell with a `CleuEvent` trigger
+/// counting its casts.
+pub fn extract_timeline(
+    encounters: &[CombatLogEncounter],
+    encounter_id: i64,
+    phase_spells: &[u32],
+) -> Vec<ExtractedAttack> {
+    let encounters = encounters
+        .iter()
+        .filter(|encounter| encounter.encounter_id == encounter_id)
+        .collect::<Vec<_>>();
+    let types = encounters
+        .iter()
+        .flat_map(|encounter| boss_events(encounter))
+        .map(|event| (event.spell_id, event.r#type.clone()))
+        .into_group_map();
+    let preferred = types
+        .into_iter()
+        .filter_map(|(spell_id, types)| {
+            let r#type = PREFERRED_TYPES
+                .into_iter()
+                .find(|r#type| types.contains(r#type))?;
+            Some((spell_id, r#type))
+        })
+        .collect::<HashMap<_, _>>();
+
+    // occurrence key: spell, phase trigger and the count of the spell within that phase
+    let mut samples = HashMap::<(u32, Option<CleuEvent>, usize), Vec<(Duration, Duration)>>::new();
+    let mut names = HashMap::new();
+    for encounter in &encounters {
+        let mut last = HashMap::<u32, Duration>::new();
+        let mut phase = None::<(CleuEvent, Duration)>;
+        let mut phase_casts = HashMap::<u32, u64>::new();
+        let mut counts = HashMap::<(u32, Option<CleuEvent>), usize>::new();
+
+        for event in boss_events(encounter) {
+            if preferred.get(&event.spell_id) != Some(&event.r#type) {
+                continue;
+            }
+            if last
+                .insert(event.spell_id, event.time)
+                .is_some_and(|previous| event.time.saturating_sub(previous) < BURST)
+            {
+                continue;
+            }
+            names
+                .entry(event.spell_id)
+                .or_insert_with(|| event.spell_name.clone());
+
+            if phase_spells.contains(&event.spell_id) {
+                let counter = phase_casts.entry(event.spell_id).or_default();
+                *counter += 1;
+                phase = Some((trigger(event, *counter), event.time));
+            }
+            let (trigger, phase_start) = match &phase {
+                Some((trigger, start)) => (Some(trigger.clone()), *start),
+                None => (None, Duration::ZERO),
+            };
+            let count = counts.entry((event.spell_id, trigger.clone())).or_default();
+            *count += 1;
+            samples
+                .entry((event.spell_id, trigger, *count))
+                .or_default()
+                .push((phase_start, event.time - phase_start));
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|((spell_id, trigger, _), samples)| {
+            let phase_start = average(samples.iter().map(|(phase_start, _)| *phase_start));
+            let dynamic_timer = average(samples.iter().map(|(_, time)| *time));
+            ExtractedAttack {
+                spell_id,
+                name: names[&spell_id].clone(),
+                timer: AttackTimer {
+                    phase_start: trigger.is_some().then_some(phase_start),
+                    dynamic_timer: Some(dynamic_timer),
+                    dynamic_trigger_cleu_event: trigger,
+                    ..Default::default()
+                },
+                pulls: samples.len(),
+            }
+        })
+        .sorted_by_key(|attack| (attack.timer.static_timer(), attack.spell_id))
+        .collect()
+}
+
+/// Copies of the attacks with the timings found in the logs. Triggered attacks take the average
+/// time of their trigger, attacks with a spell identifier take the time of the same occurrence of
+/// their spell.
+pub fn retime_attacks(attacks: &Lookup<Attack>, extracted: &[ExtractedAttack]) -> Vec<Attack> {
+    let mut occurrences = HashMap::<u32, usize>::new();
+    attacks
+        .iter()
+        .sorted_by_key(|attack| attack.timer.static_timer())
+        .filter_map(|attack| {
+            let timer = match (&attack.timer.dynamic_trigger_cleu_event, &attack.identifier) {
+                (Some(trigger), _) => {
+                    let phase = extracted.iter().find(|extracted| {
+                        extracted.timer.dynamic_trigger_cleu_event.as_ref() == Some(trigger)
+                            && u64::from(extracted.spell_id) == trigger.event
+                    })?;
+                    AttackTimer {
+                        phase_start: phase.timer.phase_start,
+                        ..attack.timer.clone()
+                    }
+                }
+                (None, Some(Identifier::Spell(spell_id))) => {
+                    let occurrence = occurrences.entry(*spell_id).or_default();
+                    *occurrence += 1;
+                    let extracted = extracted
+                        .iter()
+                        .filter(|extracted| extracted.spell_id == *spell_id)
+                        .nth(*occurrence - 1)?;
+                    AttackTimer {
+                        dynamic_timer: Some(extracted.timer.static_timer()),
+                        ..Default::default()
+                    }
+                }
+                _ => return None,
+            };
+            (timer != attack.timer).then(|| Attack {
+                timer,
+                ..attack.clone()
+            })
+        })
+        .collect()
+}
+
+fn boss_events(encounter: &CombatLogEncounter) -> impl Iterator<Item = &CombatLogEvent> {
+    encounter.events.iter().filter(|event| {
+        !event.source.is_player()
+            && event.source.is_hostile()
+            && PREFERRED_TYPES.contains(&event.r#type)
+    })
+}
+
+fn trigger(event: &CombatLogEvent, counter: u64) -> CleuEvent {
+    CleuEvent {
+        r#type: event.r#type.clone(),
+        event: u64::from(event.spell_id),
+        counter,
+    }
+}
+
+fn average(times: impl ExactSizeIterator<Item = Duration>) -> TimeStep {
+    let count = times.len().max(1) as f64;
+    let seconds = times.map(|time| time.as_secs_f64()).sum::<f64>() / count;
+    TimeStep::from(Duration::from_secs_f64(seconds.round()))
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::FromMinutesSeconds;
+
+    use crate::combat_log::parse_combat_log;
+
+    use super::*;
+
+    fn pull(aoe: &str, knock: &str) -> String {
+        format!(
+            r#"4/19 20:00:00.000  ENCOUNTER_START,2688,"Kazzara, the Hellforged",16,20,2569
+4/19 20:00:{knock}.000  SPELL_CAST_SUCCESS,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,407196,"Dread Rifts",0x20
+4/19 20:00:{knock}.500  SPELL_AURA_APPLIED,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-1,"Test-Test-EU",0x514,0x0,407196,"Dread Rifts",0x20,DEBUFF
+4/19 20:00:{knock}.600  SPELL_AURA_APPLIED,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-2,"Other-Test-EU",0x514,0x0,407196,"Dread Rifts",0x20,DEBUFF
+4/19 20:01:{aoe}.000  SPELL_CAST_START,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,401316,"Hellsteel Carnage",0x1
+4/19 20:01:40.000  SPELL_CAST_SUCCESS,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,407196,"Dread Rifts",0x20
+4/19 20:01:41.000  SPELL_CAST_SUCCESS,Player-1-1,"Test-Test-EU",0x514,0x0,0000000000000000,nil,0x80000000,0x80000000,740,"Tranquility",0x8
+4/19 20:02:00.000  ENCOUNTER_END,2688,"Kazzara, the Hellforged",16,20,0,120000
+"#
+        )
+    }
+
+    #[test]
+    fn averages_pulls_and_times_from_phase_spells() {
+        let log = pull("18", "16") + &pull("22", "18");
+        let encounters = parse_combat_log(&log);
+
+        let timeline = extract_timeline(&encounters, 2688, &[401316]);
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[0].spell_id, 407196);
+        assert_eq!(timeline[0].name, "Dread Rifts");
+        assert_eq!(timeline[0].pulls, 2);
+        assert_eq!(
+            timeline[0].timer.dynamic_timer,
+            Some(TimeStep::mm_ss(0, 17))
+        );
+        assert_eq!(timeline[0].timer.dynamic_trigger_cleu_event, None);
+
+        let phase = CleuEvent {
+            r#type: CleuEventType::SpellCastStart,
+            event: 401316,
+            counter: 1,
+        };
+        assert_eq!(timeline[1].spell_id, 401316);
+        assert_eq!(timeline[1].timer.phase_start, Some(TimeStep::mm_ss(1, 20)));
+        assert_eq!(timeline[1].timer.dynamic_timer, Some(TimeStep::mm_ss(0, 0)));
+        assert_eq!(
+            timeline[1].timer.dynamic_trigger_cleu_event,
+            Some(phase.clone())
+        );
+        assert_eq!(timeline[2].spell_id, 407196);
+        assert_eq!(
+            timeline[2].timer.dynamic_timer,
+            Some(TimeStep::mm_ss(0, 20))
+        );
+        assert_eq!(timeline[2].timer.dynamic_trigger_cleu_event, Some(phase));
+    }
+
+    #[test]
+    fn retimes_triggered_and_identified_attacks() {
+        let encounters = parse_combat_log(&pull("22", "18"));
+        let timeline = extract_timeline(&encounters, 2688, &[401316]);
+        let aoe = Attack {
+            uuid: AttackUuid::random(),
+            name: LocalizedString::constant("80% HP AoE"),
+            identifier: None,
+            power: NotNan::new(1.0).unwrap(),
+            r#type: AttackType::RaidDamage,
+            timer: AttackTimer {
+                phase_start: Some(TimeStep::mm_ss(1, 20)),
+                dynamic_timer: Some(TimeStep::mm_ss(0, 3)),
+                dynamic_trigger_cleu_event: Some(CleuEvent {
+                    r#type: CleuEventType::SpellCastStart,
+                    event: 401316,
+                    counter: 1,
+                }),
+                ..Default::default()
+            },
+        };
+        let rifts = Attack {
+            uuid: AttackUuid::random(),
+            name: LocalizedString::constant("Dread Rifts"),
+            identifier: Some(Identifier::Spell(407196)),
+            timer: AttackTimer {
+                dynamic_timer: Some(TimeStep::mm_ss(0, 15)),
+                ..Default::default()
+            },
+            ..aoe.clone()
+        };
+        let attacks = [aoe, rifts].into_iter().collect::<Lookup<_>>();
+
+        let retimed = retime_attacks(&attacks, &timeline);
+
+        assert_eq!(retimed.len(), 2);
+        assert_eq!(retimed[0].timer.dynamic_timer, Some(TimeStep::mm_ss(0, 18)));
+        assert_eq!(retimed[1].timer.phase_start, Some(TimeStep::mm_ss(1, 22)));
+        assert_eq!(retimed[1].timer.dynamic_timer, Some(TimeStep::mm_ss(0, 3)));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use fight_domain::{AttackTimer, AttackUuid, CharacterUuid, CleuEventType, SpellUuid, TimeStep};
+
+use crate::combat_log::CombatLogEncounter;
+use crate::note_import::note_name_matches;
+
+/// Casts this close to the planned time count as on time.
+pub const ON_TIME_TOLERANCE: Duration = Duration::from_secs(3);
+/// Casts further away than this from the planned time belong to another assignment, even for
+/// spells with a shorter cooldown.
+const MIN_CAST_WINDOW: Duration = Duration::from_secs(30);
+
+/// Assignment of the plan, with everything needed to find its cast in a log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCast {
+    pub character: CharacterUuid,
+    pub character_name: String,
+    pub spell: SpellUuid,
+    pub spell_id: u32,
+    pub cooldown: TimeStep,
+    pub attack: AttackUuid,
+    pub timer: AttackTimer,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CastTiming {
+    OnTime,
+    Early,
+    Late,
+    Missed,
+    /// The pull ended before the attack happened.
+    NotDue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CastVerification {
+    pub planned: PlannedCast,
+    pub expected: Option<Duration>,
+    pub cast: Option<Duration>,
+    pub timing: CastTiming,
+}
+
+/// Lines up the successful casts of the pull with the planned ones, every cast counts for at most
+/// one assignment, the closest one to its planned time.
+pub fn verify_casts(
+    planned: Vec<PlannedCast>,
+    encounter: &CombatLogEncounter,
+) -> Vec<CastVerification> {
+    let mut used = HashSet::new();
+    let mut planned = planned
+        .into_iter()
+        .map(|planned| (encounter.attack_time(&planned.timer), planned))
+        .collect::<Vec<_>>();
+    planned.sort_by_key(|(expected, _)| (expected.is_none(), *expected));
+
+    planned
+        .into_iter()
+        .map(|(expected, planned)| {
+            let Some(expected) = expected else {
+                return CastVerification {
+                    planned,
+                    expected: None,
+                    cast: None,
+                    timing: CastTiming::NotDue,
+                };
+            };
+            let window = Duration::from(planned.cooldown).max(MIN_CAST_WINDOW);
+            let cast = encounter
+                .events
+                .iter()
+                .enumerate()
+                .filter(|(index, event)| {
+                    event.r#type == CleuEventType::SpellCastSuccess
+                        && event.spell_id == planned.spell_id
+                        && event.source.is_player()
+                        && note_name_matches(&event.source.name, &planned.character_name)
+                        && event.time.abs_diff(expected) <= window
+                        && !used.contains(index)
+                })
+                .min_by_key(|(_, event)| event.time.abs_diff(expected));
+            let Some((index, cast)) = cast else {
+                return CastVerification {
+                    planned,
+                    expected: Some(expected),
+                    cast: None,
+                    timing: CastTiming::Missed,
+                };
+            };
+            used.insert(index);
+
+            let timing = if cast.time.abs_diff(expected) <= ON_TIME_TOLERANCE {
+                CastTiming::OnTime
+            } else if cast.time < expected {
+                CastTiming::Early
+            } else {
+                CastTiming::Late
+            };
+            CastVerification {
+                planned,
+                expected: Some(expected),
+                cast: Some(cast.time),
+                timing,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::FromMinutesSeconds;
+
+    use crate::combat_log::parse_combat_log;
+    use crate::combat_log::tests::LOG;
+
+    use super::*;
+
+    fn tranquility(name: &str, at: TimeStep) -> PlannedCast {
+        PlannedCast {
+            character: CharacterUuid::new(),
+            character_name: name.to_string(),
+            spell: SpellUuid::random(),
+            spell_id: 740,
+            cooldown: TimeStep::mm_ss(3, 0),
+            attack: AttackUuid::random(),
+            timer: AttackTimer {
+                dynamic_timer: Some(at),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn reports_timing_per_assignment() {
+        let encounter = &parse_combat_log(LOG)[0];
+        let planned = vec![
+            tranquility("Test", TimeStep::mm_ss(0, 3)),
+            tranquility("Test", TimeStep::mm_ss(1, 0)),
+            tranquility("Other", TimeStep::mm_ss(0, 10)),
+            tranquility("Test", TimeStep::mm_ss(5, 0)),
+        ];
+
+        let timings = verify_casts(planned, encounter)
+            .into_iter()
+            .map(|verification| (verification.planned.character_name, verification.timing))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            timings,
+            vec![
+                ("Test".to_string(), CastTiming::OnTime),
+                ("Other".to_string(), CastTiming::Missed),
+                ("Test".to_string(), CastTiming::Missed),
+                ("Test".to_string(), CastTiming::NotDue),
+            ]
+        );
+    }
+
+    #[test]
+    fn late_cast() {
+        let encounter = &parse_combat_log(LOG)[0];
+
+        let verification = verify_casts(vec![tranquility("Test", TimeStep::zero())], encounter);
+
+        assert_eq!(verification[0].timing, CastTiming::Late);
+        assert_eq!(verification[0].cast, Some(Duration::from_millis(3877)));
+    }
+}
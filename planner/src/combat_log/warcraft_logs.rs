@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use fight_domain::CleuEventType;
+
+use crate::combat_log::{CombatLogDamage, CombatLogEncounter, CombatLogEvent, CombatLogUnit};
+
+// unit flags of the advanced combat log for a friendly raid member and a hostile npc
+const PLAYER_FLAGS: u32 = 0x514;
+const NPC_FLAGS: u32 = 0xa48;
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    data: ResponseData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseData {
+    report_data: ReportData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportData {
+    report: Report,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    #[serde(default)]
+    fights: Vec<Fight>,
+    master_data: Option<MasterData>,
+    events: Option<Events>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fight {
+    #[serde(rename = "encounterID")]
+    encounter_id: i64,
+    name: String,
+    difficulty: Option<u32>,
+    kill: Option<bool>,
+    start_time: u64,
+    end_time: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MasterData {
+    #[serde(default)]
+    actors: Vec<Actor>,
+    #[serde(default)]
+    abilities: Vec<Ability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Actor {
+    id: i64,
+    name: String,
+    server: Option<String>,
+    r#type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ability {
+    #[serde(rename = "gameID")]
+    game_id: u32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Events {
+    data: Vec<Event>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Event {
+    timestamp: u64,
+    r#type: String,
+    #[serde(rename = "sourceID", default = "no_actor")]
+    source_id: i64,
+    #[serde(rename = "targetID", default = "no_actor")]
+    target_id: i64,
+    #[serde(rename = "abilityGameID", default)]
+    ability_game_id: u32,
+    #[serde(default)]
+    amount: u64,
+    #[serde(default)]
+    absorbed: u64,
+    #[serde(default)]
+    tick: bool,
+}
+
+fn no_actor() -> i64 {
+    -1
+}
+
+/// Encounters of a Warcraft Logs report, from the responses of its v2 GraphQL API. Fights,
+/// `masterData` with actors and abilities and the events can come in separate responses, such
+/// as one per page of `Casts` and `DamageTaken` events.
+pub fn parse_warcraft_logs(
+    responses: &[&str],
+) -> Result<Vec<CombatLogEncounter>, serde_json::Error> {
+    let mut report = Report::default();
+    let mut master_data = MasterData::default();
+    let mut events = vec![];
+    for response in responses {
+        let response = serde_json::from_str::<Response>(response)?
+            .data
+            .report_data
+            .report;
+        report.fights.extend(response.fights);
+        if let Some(data) = response.master_data {
+            master_data.actors.extend(data.actors);
+            master_data.abilities.extend(data.abilities);
+        }
+        events.extend(response.events.into_iter().flat_map(|events| events.data));
+    }
+    events.sort_by_key(|event| event.timestamp);
+
+    let actors = master_data
+        .actors
+        .iter()
+        .map(|actor| (actor.id, actor))
+        .collect::<HashMap<_, _>>();
+    let unit = |id: i64| {
+        let Some(actor) = actors.get(&id) else {
+            return CombatLogUnit::default();
+        };
+        match actor.r#type.as_str() {
+            "Player" => CombatLogUnit {
+                guid: format!("Player-{id}"),
+                name: match &actor.server {
+                    Some(server) => format!("{}-{server}", actor.name),
+                    None => actor.name.clone(),
+                },
+                flags: PLAYER_FLAGS,
+            },
+            "Pet" => CombatLogUnit {
+                guid: format!("Pet-{id}"),
+                name: actor.name.clone(),
+                flags: PLAYER_FLAGS,
+            },
+            _ => CombatLogUnit {
+                guid: format!("Creature-{id}"),
+                name: actor.name.clone(),
+                flags: NPC_FLAGS,
+            },
+        }
+    };
+    let spell_names = master_data
+        .abilities
+        .iter()
+        .map(|ability| (ability.game_id, ability.name.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    let encounters = report
+        .fights
+        .iter()
+        .filter(|fight| fight.encounter_id != 0)
+        .map(|fight| {
+            let mut encounter = CombatLogEncounter {
+                encounter_id: fight.encounter_id,
+                name: fight.name.clone(),
+                difficulty_id: fight.difficulty.map(difficulty_id).unwrap_or_default(),
+                kill: fight.kill,
+                duration: Duration::from_millis(fight.end_time.saturating_sub(fight.start_time)),
+                events: vec![],
+                damage: vec![],
+            };
+            for event in events
+                .iter()
+                .filter(|event| (fight.start_time..=fight.end_time).contains(&event.timestamp))
+            {
+                let time = Duration::from_millis(event.timestamp - fight.start_time);
+                let spell_name = spell_names
+                    .get(&event.ability_game_id)
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string();
+                if let Some(r#type) = cleu_event_type(&event.r#type) {
+                    encounter.events.push(CombatLogEvent {
+                        time,
+                        r#type,
+                        source: unit(event.source_id),
+                        target: unit(event.target_id),
+                        spell_id: event.ability_game_id,
+                        spell_name,
+                    });
+                } else if event.r#type == "damage" {
+                    let target = unit(event.target_id);
+                    if !target.is_player() {
+                        continue;
+                    }
+                    encounter.damage.push(CombatLogDamage {
+                        time,
+                        source: unit(event.source_id),
+                        target,
+                        spell_id: event.ability_game_id,
+                        spell_name,
+                        amount: event.amount + event.absorbed,
+                        periodic: event.tick,
+                    });
+                }
+            }
+            encounter
+        })
+        .collect();
+    Ok(encounters)
+}
+
+fn cleu_event_type(event: &str) -> Option<CleuEventType> {
+    match event {
+        "cast" => Some(CleuEventType::SpellCastSuccess),
+        "begincast" => Some(CleuEventType::SpellCastStart),
+        "applybuff" | "applydebuff" => Some(CleuEventType::SpellAuraApplied),
+        "removebuff" | "removedebuff" => Some(CleuEventType::SpellAuraRemoved),
+        _ => None,
+    }
+}
+
+/// Warcraft Logs numbers raid difficulties on its own, the log uses the game's ids.
+fn difficulty_id(difficulty: u32) -> u32 {
+    match difficulty {
+        1 => 17,
+        3 => 14,
+        4 => 15,
+        5 => 16,
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::{
+        AttackTimer, AttackUuid, CharacterUuid, FromMinutesSeconds, SpellUuid, TimeStep,
+    };
+
+    use crate::combat_log::{
+        damage_taken, extract_timeline, parse_combat_log, verify_casts, CastTiming, PlannedCast,
+    };
+
+    use super::*;
+
+    const FIGHTS: &str = include_str!("fixtures/warcraft_logs_fights.json");
+    const CASTS: &str = include_str!("fixtures/warcraft_logs_casts.json");
+    const DAMAGE_TAKEN: &str = include_str!("fixtures/warcraft_logs_damage_taken.json");
+
+    fn encounters() -> Vec<CombatLogEncounter> {
+        parse_warcraft_logs(&[FIGHTS, CASTS, DAMAGE_TAKEN]).unwrap()
+    }
+
+    #[test]
+    fn parses_fights_and_events() {
+        let encounters = encounters();
+
+        assert_eq!(encounters.len(), 2);
+        let encounter = &encounters[0];
+        assert_eq!(encounter.encounter_id, 2688);
+        assert_eq!(encounter.name, "Kazzara, the Hellforged");
+        assert_eq!(encounter.difficulty_id, 16);
+        assert_eq!(encounter.kill, Some(false));
+        assert_eq!(encounter.duration, Duration::from_secs(120));
+        assert_eq!(encounter.events[0].source.name, "Test-Draenor");
+        assert_eq!(encounter.events[0].spell_name, "Tranquility");
+        assert_eq!(encounter.damage.len(), 4);
+        assert_eq!(encounter.damage[0].amount, 100000);
+    }
+
+    #[test]
+    fn matches_combat_log_results() {
+        let log = r#"4/19 20:00:00.000  ENCOUNTER_START,2688,"Kazzara, the Hellforged",16,20,2569
+4/19 20:00:04.000  SPELL_CAST_SUCCESS,Player-1-1,"Test-Draenor-EU",0x514,0x0,0000000000000000,nil,0x80000000,0x80000000,740,"Tranquility",0x8
+4/19 20:00:20.000  SPELL_CAST_START,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,401316,"Hellsteel Carnage",0x1
+4/19 20:00:20.500  SPELL_DAMAGE,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-1,"Test-Draenor-EU",0x514,0x0,401319,"Hellsteel Carnage",0x1,Player-1-1,0000000000000000,1,1,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,70,90000,90000,-1,1,0,0,10000,nil,nil,nil
+4/19 20:00:20.500  SPELL_DAMAGE,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-2,"Other-Draenor-EU",0x514,0x0,401319,"Hellsteel Carnage",0x1,Player-1-2,0000000000000000,1,1,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,70,100000,100000,-1,1,0,0,0,nil,nil,nil
+4/19 20:00:50.000  SPELL_CAST_START,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,0000000000000000,nil,0x80000000,0x80000000,401316,"Hellsteel Carnage",0x1
+4/19 20:00:50.500  SPELL_DAMAGE,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-1,"Test-Draenor-EU",0x514,0x0,401319,"Hellsteel Carnage",0x1,Player-1-1,0000000000000000,1,1,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,70,50000,50000,-1,1,0,0,0,nil,nil,nil
+4/19 20:00:50.500  SPELL_DAMAGE,Creature-0-1,"Kazzara, the Hellforged",0x10a48,0x0,Player-1-2,"Other-Draenor-EU",0x514,0x0,401319,"Hellsteel Carnage",0x1,Player-1-2,0000000000000000,1,1,0,0,0,0,-1,0,0,0,1.00,2.00,2166,3.14,70,50000,50000,-1,1,0,0,0,nil,nil,nil
+4/19 20:02:00.000  ENCOUNTER_END,2688,"Kazzara, the Hellforged",16,20,0,120000
+"#;
+        let from_log = parse_combat_log(log);
+        let from_report = encounters();
+
+        assert_eq!(
+            extract_timeline(&from_report, 2688, &[]),
+            extract_timeline(&from_log, 2688, &[])
+        );
+        assert_eq!(
+            damage_taken(&from_report, 2688),
+            damage_taken(&from_log, 2688)
+        );
+        let planned = vec![PlannedCast {
+            character: CharacterUuid::new(),
+            character_name: "Test".to_string(),
+            spell: SpellUuid::random(),
+            spell_id: 740,
+            cooldown: TimeStep::mm_ss(3, 0),
+            attack: AttackUuid::random(),
+            timer: AttackTimer {
+                dynamic_timer: Some(TimeStep::mm_ss(0, 3)),
+                ..Default::default()
+            },
+        }];
+        let verification = verify_casts(planned.clone(), &from_report[0]);
+        assert_eq!(verification, verify_casts(planned, &from_log[0]));
+        assert_eq!(verification[0].timing, CastTiming::OnTime);
+    }
+}
@@ -0,0 +1,74 @@
+/// Characters Discord accepts in a single message.
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Packs blocks of text into as few Discord messages as possible, keeping each block in one
+/// message unless it is too long on its own, in which case it is split at its lines.
+pub fn discord_messages(blocks: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut messages = vec![];
+    let mut current = String::new();
+    for block in blocks {
+        let pieces = if length(&block) > DISCORD_MESSAGE_LIMIT {
+            block.lines().flat_map(split_line).collect()
+        } else {
+            vec![block]
+        };
+        for piece in pieces {
+            if !current.is_empty() && length(&current) + 1 + length(&piece) > DISCORD_MESSAGE_LIMIT
+            {
+                messages.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+fn split_line(line: &str) -> Vec<String> {
+    let chars = line.chars().collect::<Vec<_>>();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(DISCORD_MESSAGE_LIMIT)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+fn length(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_respect_the_limit() {
+        let line = "a".repeat(900);
+        let block = [line.clone(), line.clone(), line.clone()].join("\n");
+        let long_line = "b".repeat(DISCORD_MESSAGE_LIMIT + 10);
+
+        let messages = discord_messages(["header".to_string(), block, long_line]);
+
+        assert!(messages
+            .iter()
+            .all(|message| length(message) <= DISCORD_MESSAGE_LIMIT));
+        assert_eq!(messages[0], format!("header\n{line}\n{line}"));
+        assert_eq!(messages[1], line);
+        assert_eq!(messages[2], "b".repeat(DISCORD_MESSAGE_LIMIT));
+        assert_eq!(messages[3], "b".repeat(10));
+    }
+
+    #[test]
+    fn short_blocks_share_a_message() {
+        let messages = discord_messages(["a".to_string(), "b".to_string()]);
+
+        assert_eq!(messages, vec!["a\nb".to_string()]);
+    }
+}
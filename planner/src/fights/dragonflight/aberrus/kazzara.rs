@@ -10,6 +10,7 @@ use fight_domain::{
     Attack, AttackTimer, AttackType, AttackUuid, CleuEvent, CleuEventType, FromMinutesSeconds,
     Lookup, TimeStep,
 };
+use i18n::LocalizedString;
 use uuid::uuid;
 
 use crate::{Difficulty, PlannerFight, PlannerFightData};
@@ -20,6 +21,7 @@ pub struct Kazzara {
 }
 
 impl Kazzara {
+    /// Only the mythic timeline is known, heroic is not offered until it is taken from logs.
     pub fn mythic(
         instance_info: Option<&JournalInstanceResponse>,
         encounter_info: Option<&JournalEncounterResponse>,
@@ -61,13 +63,18 @@ impl PlannerFight for Kazzara {
         &self.fight_data
     }
 
+    fn difficulty(&self) -> Difficulty {
+        Difficulty::Mythic
+    }
+
     fn attacks(&self) -> Lookup<Attack> {
         let eighty_percent_timer = self.props.eighty_percent_timer;
         let sixty_percent_timer = self.props.sixty_percent_timer;
         let forty_percent_timer = self.props.forty_percent_timer;
         let on_pull = Attack {
             uuid: AttackUuid::new(uuid!("755af363-d688-4147-9e30-7bf0f9bf00f9")),
-            name: "On Pull".to_string(),
+            name: LocalizedString::constant("On Pull"),
+            identifier: None,
             power: NotNan::zero(),
             r#type: AttackType::Generic,
             timer: AttackTimer {
@@ -78,7 +85,8 @@ impl PlannerFight for Kazzara {
 
         let eighty_percent_aoe = Attack {
             uuid: AttackUuid::new(uuid!("53d6e795-e1d1-4f79-a250-e2bfe07abbbd")),
-            name: "80% HP AoE".to_string(),
+            name: LocalizedString::constant("80% HP AoE"),
+            identifier: None,
             power: NotNan::one(),
             r#type: AttackType::RaidDamage,
             timer: AttackTimer {
@@ -95,7 +103,8 @@ impl PlannerFight for Kazzara {
 
         let sixty_percent_aoe = Attack {
             uuid: AttackUuid::new(uuid!("fdc9c894-985f-4758-81d9-abf2280d5398")),
-            name: "60% HP AoE".to_string(),
+            name: LocalizedString::constant("60% HP AoE"),
+            identifier: None,
             power: NotNan::one(),
             r#type: AttackType::RaidDamage,
             timer: AttackTimer {
@@ -112,7 +121,8 @@ impl PlannerFight for Kazzara {
 
         let forty_percent_aoe = Attack {
             uuid: AttackUuid::new(uuid!("f15d1724-2a77-4238-9f73-97849121afd6")),
-            name: "40% HP AoE".to_string(),
+            name: LocalizedString::constant("40% HP AoE"),
+            identifier: None,
             power: NotNan::one(),
             r#type: AttackType::RaidDamage,
             timer: AttackTimer {
@@ -129,7 +139,8 @@ impl PlannerFight for Kazzara {
 
         let knock_aoe = move |uuid, timer: TimeStep| Attack {
             uuid,
-            name: "Knock AoE".to_string(),
+            name: LocalizedString::constant("Knock AoE"),
+            identifier: None,
             power: NotNan::one(),
             r#type: AttackType::RaidDamage,
             timer: AttackTimer {
@@ -0,0 +1,57 @@
+use fight_domain::{Attack, Lookup};
+use i18n::LocalizedString;
+
+use crate::combat_log::{AttackDamage, ExtractedAttack};
+use crate::{Difficulty, PlannerFight, PlannerFightData};
+
+/// Fight built from the boss timeline of combat log pulls, for bosses without a timeline.
+pub struct LogFight {
+    fight_data: Option<PlannerFightData>,
+    difficulty: Difficulty,
+    attacks: Lookup<Attack>,
+}
+
+impl LogFight {
+    pub fn new(
+        name: &str,
+        encounter_id: i64,
+        difficulty: Difficulty,
+        timeline: &[ExtractedAttack],
+        damage: &[AttackDamage],
+    ) -> Self {
+        let fight_data = PlannerFightData {
+            instance_id: 0,
+            instance_name: LocalizedString::constant("Custom"),
+            encounter_id,
+            encounter_name: LocalizedString::constant(name),
+            encounter_description: LocalizedString::constant(""),
+            difficulty,
+            image_path: "",
+            image_offset: 0,
+            abilities: vec![],
+        };
+
+        Self {
+            fight_data: Some(fight_data),
+            difficulty,
+            attacks: timeline
+                .iter()
+                .map(|attack| attack.to_attack(damage))
+                .collect(),
+        }
+    }
+}
+
+impl PlannerFight for LogFight {
+    fn data(&self) -> &Option<PlannerFightData> {
+        &self.fight_data
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn attacks(&self) -> Lookup<Attack> {
+        self.attacks.clone()
+    }
+}
@@ -1 +1,3 @@
 pub mod dragonflight;
+pub mod log_fight;
+pub mod note_fight;
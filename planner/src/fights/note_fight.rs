@@ -0,0 +1,154 @@
+use ordered_float::NotNan;
+
+use fight_domain::{Attack, AttackTimer, AttackType, AttackUuid, Identifier, Lookup};
+use i18n::LocalizedString;
+
+use crate::{parse_note, Difficulty, NoteIcon, NoteTime, PlannerFight, PlannerFightData};
+
+/// Fight built from the `{time}` lines of a pasted MRT note, for bosses without a timeline.
+pub struct NoteFight {
+    fight_data: Option<PlannerFightData>,
+    difficulty: Difficulty,
+    attacks: Lookup<Attack>,
+}
+
+impl NoteFight {
+    pub fn new(name: &str, difficulty: Difficulty, note: &str) -> Self {
+        let fight_data = PlannerFightData {
+            instance_id: 0,
+            instance_name: LocalizedString::constant("Custom"),
+            encounter_id: 0,
+            encounter_name: LocalizedString::constant(name),
+            encounter_description: LocalizedString::constant(""),
+            difficulty,
+            image_path: "",
+            image_offset: 0,
+            abilities: vec![],
+        };
+
+        // one attack per distinct time and trigger, named after the first line that has a name
+        let mut times = Vec::<(NoteTime, AttackTimer, String, Option<Identifier>)>::new();
+        for line in parse_note(note) {
+            // icons in front of any name are the boss ability
+            let identifier = line
+                .entries
+                .iter()
+                .filter(|entry| entry.name.is_empty())
+                .flat_map(|entry| &entry.icons)
+                .find_map(|icon| match icon {
+                    NoteIcon::Spell(id) => Some(Identifier::Spell(*id)),
+                    _ => None,
+                });
+            match times.iter_mut().find(|(time, _, _, _)| *time == line.time) {
+                Some((_, _, name, existing)) => {
+                    if name.is_empty() {
+                        *name = line.label;
+                    }
+                    if existing.is_none() {
+                        *existing = identifier;
+                    }
+                }
+                None => {
+                    let phase_start = line
+                        .static_timer
+                        .filter(|static_timer| line.time.trigger.is_some() && *static_timer >= line.time.timer)
+                        .map(|static_timer| static_timer - line.time.timer);
+                    let timer = AttackTimer {
+                        phase_start,
+                        dynamic_timer: Some(line.time.timer),
+                        dynamic_trigger_cleu_event: line.time.trigger.clone(),
+                        ..Default::default()
+                    };
+                    times.push((line.time, timer, line.label, identifier));
+                }
+            }
+        }
+
+        let attacks = times
+            .into_iter()
+            .map(|(time, timer, name, identifier)| Attack {
+                uuid: AttackUuid::random(),
+                name: if name.is_empty() {
+                    LocalizedString::constant(&format!("Attack {time}"))
+                } else {
+                    LocalizedString::constant(&name)
+                },
+                identifier,
+                power: NotNan::new(1.0).unwrap(),
+                r#type: AttackType::RaidDamage,
+                timer,
+            })
+            .collect();
+
+        Self {
+            fight_data: Some(fight_data),
+            difficulty,
+            attacks,
+        }
+    }
+}
+
+impl PlannerFight for NoteFight {
+    fn data(&self) -> &Option<PlannerFightData> {
+        &self.fight_data
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    fn attacks(&self) -> Lookup<Attack> {
+        self.attacks.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::{CleuEvent, CleuEventType, FromMinutesSeconds, TimeStep};
+
+    use super::*;
+
+    #[test]
+    fn one_attack_per_distinct_time() {
+        let fight = NoteFight::new(
+            "Custom Boss",
+            Difficulty::Mythic,
+            "Custom Boss - Mythic\n\
+            {time:00:10}00:10 - Adds - Alice {spell:740}\n\
+            {time:00:10} Bob {spell:98008}\n\
+            {time:00:03,SCS:401316:1}01:23 - {spell:401316} 80% HP AoE - Alice {spell:33891}\n\
+            {time:01:00}\n\
+            {time:00:03,SCS:401316:2}Second AoE",
+        );
+
+        let attacks = fight.attacks().into_iter().collect::<Vec<_>>();
+        assert_eq!(attacks.len(), 4);
+
+        assert_eq!(attacks[0].name, LocalizedString::constant("Adds"));
+        assert_eq!(attacks[0].identifier, None);
+        assert_eq!(attacks[0].timer.static_timer(), TimeStep::mm_ss(0, 10));
+        assert_eq!(attacks[0].timer.dynamic_trigger_cleu_event, None);
+
+        assert_eq!(attacks[1].name, LocalizedString::constant("80% HP AoE"));
+        assert_eq!(attacks[1].identifier, Some(Identifier::Spell(401316)));
+        assert_eq!(attacks[1].timer.phase_start, Some(TimeStep::mm_ss(1, 20)));
+        assert_eq!(attacks[1].timer.dynamic_timer, Some(TimeStep::mm_ss(0, 3)));
+        assert_eq!(
+            attacks[1].timer.dynamic_trigger_cleu_event,
+            Some(CleuEvent {
+                r#type: CleuEventType::SpellCastStart,
+                event: 401316,
+                counter: 1,
+            })
+        );
+
+        assert_eq!(attacks[2].name, LocalizedString::constant("Attack 01:00"));
+
+        assert_eq!(attacks[3].name, LocalizedString::constant("Second AoE"));
+        assert_eq!(attacks[3].timer.phase_start, None);
+        assert_eq!(
+            fight.data().as_ref().unwrap().encounter_name,
+            LocalizedString::constant("Custom Boss")
+        );
+    }
+}
@@ -1,17 +1,34 @@
+pub use planner_attack::*;
 pub use planner_character::*;
 pub use planner_fight::*;
+pub use planner_history::PlannerEdit;
 pub use planner_realm::*;
+pub use planner_spell::*;
 pub use planner_state::*;
 pub use planner_user::*;
 pub use as_in_game_note::*;
+pub use discord_message::*;
+pub use note_import::*;
+pub use saved_plan::*;
+pub use share_code::{ShareCodeError, SHARE_CODE_VERSION};
 
+pub mod combat_log;
 pub mod fights;
 pub mod fuzzy_search;
 pub mod planner_assignments;
+mod planner_attack;
 mod planner_character;
 mod planner_fight;
+mod planner_history;
 mod planner_realm;
+mod planner_spell;
 mod planner_state;
 mod planner_user;
 pub mod specs;
+pub mod spreadsheet;
+pub mod weak_aura;
 mod as_in_game_note;
+mod discord_message;
+mod note_import;
+mod saved_plan;
+mod share_code;
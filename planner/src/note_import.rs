@@ -0,0 +1,422 @@
+use std::fmt::{Display, Formatter};
+
+use deunicode::deunicode;
+
+use fight_domain::{AttackTimer, CleuEvent, FromMinutesSeconds, Identifier, RaidMarker, TimeStep};
+
+
+/// A line of an MRT note that starts with a `{time}` tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteLine {
+    /// 1-based line number in the note.
+    pub line: usize,
+    pub text: String,
+    pub time: NoteTime,
+    /// Time written in front of the line, e.g. the time since pull of a triggered attack.
+    pub static_timer: Option<TimeStep>,
+    /// Remaining text without tags and assigned names, usually the attack name.
+    pub label: String,
+    pub entries: Vec<NoteEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteTime {
+    pub timer: TimeStep,
+    pub trigger: Option<CleuEvent>,
+    pub phase: Option<u32>,
+}
+
+/// A name followed by the icons assigned to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteEntry {
+    pub name: String,
+    /// Set instead of a name for icons behind an `{everyone}` tag.
+    pub everyone: bool,
+    pub icons: Vec<NoteIcon>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteIcon {
+    Spell(u32),
+    /// File data id or texture path.
+    Icon(String),
+    Marker(RaidMarker),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteImportProblem {
+    UnknownAttack { line: usize, time: String },
+    UnknownCharacter { line: usize, name: String },
+    UnknownSpell { line: usize, name: String, icon: NoteIcon },
+    NotAssignable { line: usize, name: String, icon: NoteIcon },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoteImportReport {
+    pub imported: usize,
+    pub problems: Vec<NoteImportProblem>,
+}
+
+pub fn parse_note(note: &str) -> Vec<NoteLine> {
+    note.lines()
+        .enumerate()
+        .filter_map(|(i, text)| parse_line(i + 1, text))
+        .collect()
+}
+
+fn parse_line(line: usize, text: &str) -> Option<NoteLine> {
+    let stripped = strip_colors(text);
+    let mut time = None;
+    let mut entries = Vec::<NoteEntry>::new();
+    let mut last_word = None::<String>;
+    let mut words = Vec::<&str>::new();
+
+    for token in tokenize(&stripped) {
+        match token {
+            Token::Tag(tag) => {
+                if let Some(value) = tag.strip_prefix("time:") {
+                    time = time.or_else(|| parse_time(value));
+                } else if tag == "everyone" {
+                    last_word = None;
+                    entries.push(NoteEntry {
+                        name: String::new(),
+                        everyone: true,
+                        icons: vec![],
+                    });
+                } else if let Some(icon) = parse_icon(tag) {
+                    match (last_word.take(), entries.last_mut()) {
+                        (Some(name), _) => {
+                            words.pop();
+                            entries.push(NoteEntry {
+                                name,
+                                everyone: false,
+                                icons: vec![icon],
+                            })
+                        }
+                        (None, Some(entry)) => entry.icons.push(icon),
+                        (None, None) => entries.push(NoteEntry {
+                            name: String::new(),
+                            everyone: false,
+                            icons: vec![icon],
+                        }),
+                    }
+                }
+            }
+            Token::Word(word) if word.chars().any(char::is_alphabetic) => {
+                words.push(word);
+                last_word = Some(word.to_string());
+            }
+            Token::Word(word) => {
+                words.push(word);
+                last_word = None;
+            }
+        }
+    }
+
+    let static_timer = words
+        .first()
+        .filter(|word| word.contains(':'))
+        .and_then(|word| parse_timer(word));
+    let label = words
+        .into_iter()
+        .skip_while(|word| *word == "-" || parse_timer(word).is_some())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '-' || c.is_whitespace())
+        .to_string();
+
+    Some(NoteLine {
+        line,
+        text: text.to_string(),
+        time: time?,
+        static_timer,
+        label,
+        entries,
+    })
+}
+
+enum Token<'a> {
+    Tag(&'a str),
+    Word(&'a str),
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('{') {
+            match tag.find('}') {
+                Some(end) => {
+                    tokens.push(Token::Tag(&tag[..end]));
+                    rest = &tag[end + 1..];
+                }
+                None => rest = "",
+            }
+        } else {
+            let end = rest
+                .find(|c: char| c == '{' || c.is_whitespace())
+                .unwrap_or(rest.len());
+            if end > 0 {
+                tokens.push(Token::Word(&rest[..end]));
+            }
+            rest = rest[end..].trim_start();
+        }
+    }
+    tokens
+}
+
+/// Removes `|cAARRGGBB` and `|r` color codes.
+fn strip_colors(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('|') {
+        result.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        if let Some(after) = after.strip_prefix('c').and_then(|after| after.get(8..)) {
+            rest = after;
+        } else if let Some(after) = after.strip_prefix('r') {
+            rest = after;
+        } else {
+            result.push('|');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn parse_time(value: &str) -> Option<NoteTime> {
+    let mut parts = value.split(',');
+    let timer = parse_timer(parts.next()?.trim())?;
+    let mut trigger = None;
+    let mut phase = None;
+    for part in parts.map(str::trim) {
+        if let Some(number) = part.strip_prefix("p:").or_else(|| part.strip_prefix('p')) {
+            phase = number.parse().ok();
+        } else if let [r#type, event, counter] = part.split(':').collect::<Vec<_>>()[..] {
+            trigger = Some(CleuEvent {
+                r#type: r#type.parse().ok()?,
+                event: event.parse().ok()?,
+                counter: counter.parse().ok()?,
+            });
+        }
+    }
+    Some(NoteTime {
+        timer,
+        trigger,
+        phase,
+    })
+}
+
+fn parse_timer(value: &str) -> Option<TimeStep> {
+    match value.split_once(':') {
+        Some((minutes, seconds)) => Some(TimeStep::mm_ss(
+            minutes.parse().ok()?,
+            seconds.parse().ok()?,
+        )),
+        None => Some(TimeStep::mm_ss(0, value.parse().ok()?)),
+    }
+}
+
+fn parse_icon(tag: &str) -> Option<NoteIcon> {
+    if let Some(id) = tag.strip_prefix("spell:") {
+        id.trim().parse().ok().map(NoteIcon::Spell)
+    } else if let Some(icon) = tag.strip_prefix("icon:") {
+        Some(NoteIcon::Icon(icon.trim().to_string()))
+    } else if let Some(marker) = tag.strip_prefix("rt") {
+        raid_marker(marker.parse().ok()?).map(NoteIcon::Marker)
+    } else {
+        None
+    }
+}
+
+fn raid_marker(index: u8) -> Option<RaidMarker> {
+    match index {
+        1 => Some(RaidMarker::Star),
+        2 => Some(RaidMarker::Circle),
+        3 => Some(RaidMarker::Diamond),
+        4 => Some(RaidMarker::Triangle),
+        5 => Some(RaidMarker::Square),
+        6 => Some(RaidMarker::Moon),
+        7 => Some(RaidMarker::Cross),
+        8 => Some(RaidMarker::Skull),
+        _ => None,
+    }
+}
+
+impl NoteTime {
+    /// Exact match on the CLEU trigger and its timer, or on the static timer without a trigger.
+    pub fn matches(&self, timer: &AttackTimer) -> bool {
+        match (&self.trigger, &timer.dynamic_trigger_cleu_event) {
+            (Some(trigger), Some(cleu_event)) => {
+                trigger == cleu_event
+                    && timer.dynamic_timer.unwrap_or(TimeStep::zero()) == self.timer
+            }
+            (None, None) if self.phase.is_some() => {
+                timer.phase_start.is_some()
+                    && timer.dynamic_timer.unwrap_or(TimeStep::zero()) == self.timer
+            }
+            (None, None) => timer.static_timer() == self.timer,
+            _ => false,
+        }
+    }
+}
+
+impl Display for NoteTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.timer)?;
+        if let Some(trigger) = &self.trigger {
+            write!(f, ",{}:{}:{}", trigger.r#type, trigger.event, trigger.counter)?;
+        }
+        if let Some(phase) = self.phase {
+            write!(f, ",p{phase}")?;
+        }
+        Ok(())
+    }
+}
+
+impl NoteIcon {
+    pub fn matches(&self, identifier: &Identifier) -> bool {
+        match (self, identifier) {
+            (NoteIcon::Spell(id), Identifier::Spell(other)) => id == other,
+            (NoteIcon::Icon(icon), Identifier::Icon(name, file_data_id)) => {
+                *icon == file_data_id.to_string()
+                    || icon
+                        .rsplit(['\\', '/'])
+                        .next()
+                        .is_some_and(|icon| icon.eq_ignore_ascii_case(name))
+            }
+            (NoteIcon::Marker(marker), Identifier::Marker(other)) => marker == other,
+            _ => false,
+        }
+    }
+}
+
+impl Display for NoteIcon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoteIcon::Spell(id) => write!(f, "{{spell:{id}}}"),
+            NoteIcon::Icon(icon) => write!(f, "{{icon:{icon}}}"),
+            NoteIcon::Marker(marker) => write!(f, "{{rt{}}}", *marker as u8),
+        }
+    }
+}
+
+impl Display for NoteImportProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoteImportProblem::UnknownAttack { line, time } => {
+                write!(f, "Line {line}: no attack at {time}")
+            }
+            NoteImportProblem::UnknownCharacter { line, name } => {
+                write!(f, "Line {line}: no character named {name}")
+            }
+            NoteImportProblem::UnknownSpell { line, name, icon } => {
+                write!(f, "Line {line}: {name} has no spell {icon}")
+            }
+            NoteImportProblem::NotAssignable { line, name, icon } => {
+                write!(f, "Line {line}: {icon} of {name} conflicts with another assignment")
+            }
+        }
+    }
+}
+
+/// Compares character names ignoring case, accents and a `-Realm` suffix.
+pub fn note_name_matches(note_name: &str, name: &str) -> bool {
+    let note_name = note_name.split('-').next().unwrap_or_default();
+    deunicode(note_name).to_lowercase() == deunicode(name).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::CleuEventType;
+
+    use super::*;
+
+    #[test]
+    fn parse_exported_line() {
+        let lines = parse_note(
+            "Kazzara - Mythic\r\n\
+            {time:00:03,SCS:401316:1}01:23 - 80% HP AoE - Alice {spell:740} {spell:33891}  Bob {spell:98008}",
+        );
+
+        assert_eq!(
+            lines,
+            vec![NoteLine {
+                line: 2,
+                text: "{time:00:03,SCS:401316:1}01:23 - 80% HP AoE - Alice {spell:740} {spell:33891}  Bob {spell:98008}".to_string(),
+                time: NoteTime {
+                    timer: TimeStep::mm_ss(0, 3),
+                    trigger: Some(CleuEvent {
+                        r#type: CleuEventType::SpellCastStart,
+                        event: 401316,
+                        counter: 1,
+                    }),
+                    phase: None,
+                },
+                static_timer: Some(TimeStep::mm_ss(1, 23)),
+                label: "80% HP AoE".to_string(),
+                entries: vec![
+                    NoteEntry {
+                        name: "Alice".to_string(),
+                        everyone: false,
+                        icons: vec![NoteIcon::Spell(740), NoteIcon::Spell(33891)],
+                    },
+                    NoteEntry {
+                        name: "Bob".to_string(),
+                        everyone: false,
+                        icons: vec![NoteIcon::Spell(98008)],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_hand_written_line() {
+        let lines = parse_note("{time:80,p2}|cfff48cbaPaladin|r{spell:31821} {rt3}Shaman-Draenor {icon:Interface\\Icons\\spell_nature_reincarnation}");
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].time.timer, TimeStep::mm_ss(1, 20));
+        assert_eq!(lines[0].time.phase, Some(2));
+        assert_eq!(lines[0].static_timer, None);
+        assert_eq!(lines[0].label, "");
+        assert_eq!(
+            lines[0].entries,
+            vec![
+                NoteEntry {
+                    name: "Paladin".to_string(),
+                    everyone: false,
+                    icons: vec![NoteIcon::Spell(31821), NoteIcon::Marker(RaidMarker::Diamond)],
+                },
+                NoteEntry {
+                    name: "Shaman-Draenor".to_string(),
+                    everyone: false,
+                    icons: vec![NoteIcon::Icon(
+                        "Interface\\Icons\\spell_nature_reincarnation".to_string()
+                    )],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_without_time_are_skipped() {
+        assert!(parse_note("Healers:\nAlice {spell:740}\n{time:abc}").is_empty());
+    }
+
+    #[test]
+    fn icon_matches_file_data_id_and_path() {
+        let identifier = Identifier::Icon("warlock_healthstone".to_string(), 538745);
+
+        assert!(NoteIcon::Icon("538745".to_string()).matches(&identifier));
+        assert!(NoteIcon::Icon("Interface\\Icons\\Warlock_Healthstone".to_string()).matches(&identifier));
+        assert!(!NoteIcon::Spell(538745).matches(&identifier));
+    }
+
+    #[test]
+    fn names_match_without_realm_and_accents() {
+        assert!(note_name_matches("Ålice-Draenor", "alice"));
+        assert!(!note_name_matches("Bob", "Alice"));
+    }
+}
@@ -87,6 +87,16 @@ impl PlannerAssignments {
         };
     }
 
+    pub fn remove_spell(&mut self, spell: SpellUuid) {
+        self.assignments
+            .retain(|(assigned_spell, _), _| *assigned_spell != spell);
+    }
+
+    pub fn remove_attack(&mut self, attack: AttackUuid) {
+        self.assignments
+            .retain(|(_, assigned_attack), _| *assigned_attack != attack);
+    }
+
     pub fn suggested(&self) -> impl Iterator<Item = &(SpellUuid, AttackUuid)> {
         self.assignments
             .iter()
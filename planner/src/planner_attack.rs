@@ -0,0 +1,39 @@
+use ordered_float::NotNan;
+
+use fight_domain::{Attack, AttackTimer, AttackType, AttackUuid, Identifier};
+use i18n::{Locale, LocalizedString};
+
+/// A user-defined timeline entry added on top of the attacks of a fight.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CustomAttackTemplate {
+    pub name: String,
+    pub identifier: Option<Identifier>,
+    pub power: NotNan<f64>,
+    pub r#type: AttackType,
+    pub timer: AttackTimer,
+}
+
+impl CustomAttackTemplate {
+    pub fn into_attack(self, uuid: AttackUuid) -> Attack {
+        Attack {
+            uuid,
+            name: LocalizedString::constant(&self.name),
+            identifier: self.identifier,
+            power: self.power,
+            r#type: self.r#type,
+            timer: self.timer,
+        }
+    }
+}
+
+impl From<&Attack> for CustomAttackTemplate {
+    fn from(attack: &Attack) -> Self {
+        Self {
+            name: attack.name.get(Locale::EnglishUnitedStates).to_owned(),
+            identifier: attack.identifier.clone(),
+            power: attack.power,
+            r#type: attack.r#type,
+            timer: attack.timer.clone(),
+        }
+    }
+}
@@ -1,10 +1,25 @@
-use fight_domain::{AttackUuid, Character, CharacterUuid, Lookup, LookupKey, Spell, SpellUuid};
+use std::collections::{BTreeSet, HashMap};
+
+use itertools::Itertools;
+
+use fight_domain::{
+    AttackUuid, Character, CharacterUuid, Identifier, Lookup, LookupKey, Spell, SpellOverride,
+    SpellUuid,
+};
 use i18n::{Locale, LocalizedString};
 use optimizer::AssignmentState;
 
 use crate::planner_assignments::PlannerAssignments;
 use crate::planner_realm::PlannerRealm;
-use crate::specs;
+use crate::{specs, CustomSpellTemplate, SavedCharacter};
+
+/// A locked assignment that could not be carried over to the new spells of a character.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DroppedAssignment {
+    pub spell: SpellUuid,
+    pub spell_name: LocalizedString,
+    pub attack: AttackUuid,
+}
 
 #[derive(Debug, Clone)]
 pub enum PlannerCharacterTemplate {
@@ -12,6 +27,7 @@ pub enum PlannerCharacterTemplate {
         name: String,
         realm: PlannerRealm,
         class: LocalizedString,
+        spec: LocalizedString,
     },
     Custom {
         name: String,
@@ -43,6 +59,13 @@ impl PlannerCharacterTemplate {
             _ => None,
         }
     }
+
+    pub fn spec(&self) -> Option<&LocalizedString> {
+        match self {
+            PlannerCharacterTemplate::Known { spec, .. } => Some(spec),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -53,6 +76,10 @@ pub struct PlannerCharacter {
     pub class: Option<LocalizedString>,
     pub spec: Option<LocalizedString>,
     pub spells: Lookup<Spell>,
+    pub custom_spells: Lookup<Spell>,
+    pub spell_overrides: HashMap<SpellUuid, SpellOverride>,
+    /// Selected talent spell ids of the active loadout, by en_US spec name.
+    pub talents: HashMap<String, BTreeSet<u32>>,
     pub assignments: PlannerAssignments,
 }
 
@@ -63,39 +90,279 @@ impl PlannerCharacter {
             name: template.name(),
             realm: template.realm().map(ToOwned::to_owned),
             class: template.class().map(ToOwned::to_owned),
-            spec: None,
+            spec: template.spec().map(ToOwned::to_owned),
             spells: Default::default(),
+            custom_spells: Default::default(),
+            spell_overrides: Default::default(),
+            talents: Default::default(),
             assignments: Default::default(),
         };
         s.update_spells();
         s
     }
 
+    pub fn to_saved(&self) -> SavedCharacter {
+        SavedCharacter {
+            uuid: self.uuid,
+            name: self
+                .name
+                .as_ref()
+                .filter(|_| !self.is_general())
+                .map(|name| name.get(Locale::EnglishUnitedStates).to_owned()),
+            realm: self.realm.clone(),
+            class: self.class.clone(),
+            spec: self.spec.clone(),
+            enabled_spells: self
+                .spells
+                .iter()
+                .filter(|spell| spell.enabled)
+                .map(|spell| spell.uuid)
+                .collect(),
+            custom_spells: self.custom_spells.iter().cloned().collect(),
+            spell_overrides: self.spell_overrides.clone().into_iter().collect(),
+            talents: self.talents.clone().into_iter().collect(),
+            // sorted, so the same plan always saves the same
+            locked_assignments: self.assignments.locked().copied().sorted().collect(),
+        }
+    }
+
+    /// Rebuilds the spells of the saved spec, overrides of spells the spec no longer has are
+    /// dropped.
+    pub fn from_saved(saved: &SavedCharacter) -> Self {
+        let template = match &saved.name {
+            _ if saved.uuid == CharacterUuid::general() => PlannerCharacterTemplate::General,
+            Some(name) => PlannerCharacterTemplate::Custom { name: name.clone() },
+            None => PlannerCharacterTemplate::Unknown,
+        };
+        let mut character = Self::new(saved.uuid, template);
+        character.realm = saved.realm.clone();
+        character.class = saved.class.clone();
+        character.spec = saved.spec.clone();
+        character.talents = saved.talents.clone().into_iter().collect();
+        character.custom_spells = saved.custom_spells.iter().cloned().collect();
+        character.update_spells();
+        character.spell_overrides = saved
+            .spell_overrides
+            .iter()
+            .filter(|(uuid, _)| {
+                character.spells.contains_key(uuid) && !character.custom_spells.contains_key(uuid)
+            })
+            .map(|(uuid, spell_override)| (*uuid, spell_override.clone()))
+            .collect();
+        character.update_spells();
+        for spell in character.spells.iter_mut() {
+            spell.enabled = saved.enabled_spells.contains(&spell.uuid);
+        }
+        for &(spell, attack) in &saved.locked_assignments {
+            if character.spells.contains_key(&spell) {
+                character.assignments.assign_locked(spell, attack);
+            }
+        }
+        character
+    }
+
     pub fn assignment_state(&self, spell: SpellUuid, attack: AttackUuid) -> AssignmentState {
         self.assignments.get(spell, attack)
     }
 
-    pub fn change_class(&mut self, class: LocalizedString) {
+    pub fn change_class(&mut self, class: LocalizedString) -> Vec<DroppedAssignment> {
+        let spells = self.spells.clone();
+        let assignments = std::mem::take(&mut self.assignments);
+        // the spec the class is usually played as until the actual one is picked, its spells take
+        // over the assignments
+        self.spec = specs::default_spec_for_class(class.get(Locale::EnglishUnitedStates))
+            .map(LocalizedString::constant);
         self.class = Some(class);
-        self.spec = None;
-        self.spells = Lookup::default();
-        self.assignments = PlannerAssignments::default();
+        self.spell_overrides.clear();
+        self.talents.clear();
+        self.update_spells();
+        self.carry_over_assignments(&spells, &assignments)
     }
 
-    pub fn change_spec(&mut self, spec: LocalizedString) {
+    pub fn change_spec(&mut self, spec: LocalizedString) -> Vec<DroppedAssignment> {
+        let spells = self.spells.clone();
+        let assignments = std::mem::take(&mut self.assignments);
         self.spec = Some(spec);
         self.update_spells();
-        self.assignments = PlannerAssignments::default();
+        // overrides of spells only the previous spec had
+        self.spell_overrides
+            .retain(|uuid, _| self.spells.contains_key(uuid));
+        self.apply_talents();
+        self.carry_over_assignments(&spells, &assignments)
+    }
+
+    /// Locks the locked assignments of the previous spells on the current spell with the same
+    /// identifier, preferring the same and then enabled spells. Suggestions are left to the
+    /// optimizer.
+    pub fn carry_over_assignments(
+        &mut self,
+        spells: &Lookup<Spell>,
+        assignments: &PlannerAssignments,
+    ) -> Vec<DroppedAssignment> {
+        let mut dropped = vec![];
+        for &(spell, attack) in assignments.locked() {
+            let Some(previous) = spells.get(&spell) else {
+                continue;
+            };
+            let same_identifier = || {
+                self.spells
+                    .iter()
+                    .filter(|spell| spell.identifier == previous.identifier)
+            };
+            let current = self
+                .spells
+                .get(&spell)
+                .or_else(|| same_identifier().find(|spell| spell.enabled))
+                .or_else(|| same_identifier().next())
+                .map(|spell| spell.uuid);
+            match current {
+                Some(current) => self.assignments.assign_locked(current, attack),
+                None => dropped.push(DroppedAssignment {
+                    spell,
+                    spell_name: previous.name.clone(),
+                    attack,
+                }),
+            }
+        }
+        dropped
+    }
+
+    /// Replaces the known talent loadouts and enables the spells they select for the current spec.
+    pub fn set_talents(&mut self, talents: HashMap<String, BTreeSet<u32>>) {
+        self.talents = talents;
+        self.apply_talents();
+    }
+
+    /// Whether the character has a talent loadout for the spec, i.e. actually plays it.
+    pub fn plays_spec(&self, spec: &LocalizedString) -> bool {
+        self.talents.contains_key(spec.get(Locale::EnglishUnitedStates))
+    }
+
+    fn apply_talents(&mut self) {
+        let Some((class, spec)) = self.class.as_ref().zip(self.spec.as_ref()) else {
+            return;
+        };
+        let Some(talents) = self.talents.get(spec.get(Locale::EnglishUnitedStates)) else {
+            return;
+        };
+        let states = specs::spells_enabled_by_talents(
+            class.get(Locale::EnglishUnitedStates),
+            spec.get(Locale::EnglishUnitedStates),
+            talents,
+        );
+        for (uuid, enabled) in states {
+            if let Some(spell) = self.spells.get_mut(&uuid) {
+                spell.enabled = enabled;
+            }
+        }
+    }
+
+    pub fn add_custom_spell(&mut self, spell: CustomSpellTemplate) -> SpellUuid {
+        let uuid = SpellUuid::random();
+        self.custom_spells.put(spell.into_spell(uuid, true));
+        self.update_spells();
+        uuid
+    }
+
+    pub fn update_custom_spell(&mut self, uuid: SpellUuid, spell: CustomSpellTemplate) {
+        let Some(previous) = self.custom_spells.get(&uuid) else {
+            panic!("Cannot update a spell that is not a custom spell");
+        };
+        let enabled = previous.enabled;
+        self.custom_spells.put(spell.into_spell(uuid, enabled));
+        self.update_spells();
+    }
+
+    pub fn remove_custom_spell(&mut self, uuid: SpellUuid) {
+        if self.custom_spells.take(&uuid).is_some() {
+            self.assignments.remove_spell(uuid);
+            self.update_spells();
+        }
+    }
+
+    pub fn is_custom_spell(&self, uuid: SpellUuid) -> bool {
+        self.custom_spells.contains_key(&uuid)
+    }
+
+    pub fn override_spell(&mut self, uuid: SpellUuid, spell_override: SpellOverride) {
+        if self.is_custom_spell(uuid) {
+            panic!("Cannot override a custom spell, update it instead");
+        } else if !self.spells.contains_key(&uuid) {
+            panic!("Cannot override a spell the character does not have");
+        }
+        if spell_override.is_empty() {
+            self.spell_overrides.remove(&uuid);
+        } else {
+            self.spell_overrides.insert(uuid, spell_override);
+        }
+        self.update_spells();
+    }
+
+    pub fn reset_spell_override(&mut self, uuid: SpellUuid) {
+        if self.spell_overrides.remove(&uuid).is_some() {
+            self.update_spells();
+        }
     }
 
     fn update_spells(&mut self) {
-        if self.is_general() {
-            self.spells = specs::general::spells();
+        let built_in_spells = if self.is_general() {
+            specs::general::spells()
         } else if let Some((class, spec)) = self.class.as_ref().zip(self.spec.as_ref()) {
-            self.spells = specs::spells_for_spec(
+            specs::spells_for_spec(
                 class.get(Locale::EnglishUnitedStates),
                 spec.get(Locale::EnglishUnitedStates),
-            );
+            )
+        } else {
+            Lookup::default()
+        };
+
+        let previous_spells = std::mem::take(&mut self.spells);
+        self.spells = built_in_spells
+            .into_iter()
+            .map(|spell| match self.spell_overrides.get(&spell.uuid) {
+                Some(spell_override) => spell_override.apply(&spell),
+                None => spell,
+            })
+            .chain(self.custom_spells.iter().cloned())
+            .map(|mut spell| {
+                // keep toggled state and translations when rebuilding the same spells
+                if let Some(previous_spell) = previous_spells.get(&spell.uuid) {
+                    spell.enabled = previous_spell.enabled;
+                    if previous_spell.name.get(Locale::EnglishUnitedStates)
+                        == spell.name.get(Locale::EnglishUnitedStates)
+                    {
+                        spell.name = previous_spell.name.clone();
+                    }
+                }
+                spell
+            })
+            .collect();
+    }
+
+    /// Translates the names of built-in spells from their Battle.net names, keeping variant
+    /// suffixes like "3m". Custom spells and overridden names keep what the user typed.
+    pub fn localize_spells(&mut self, names: &HashMap<u32, LocalizedString>) {
+        for spell in self.spells.iter_mut() {
+            let renamed = self.custom_spells.contains_key(&spell.uuid)
+                || self
+                    .spell_overrides
+                    .get(&spell.uuid)
+                    .is_some_and(|spell_override| spell_override.name.is_some());
+            let Identifier::Spell(id) = spell.identifier else {
+                continue;
+            };
+            let Some(name) = names.get(&id).filter(|_| !renamed) else {
+                continue;
+            };
+            let Some(suffix) = spell
+                .name
+                .get(Locale::EnglishUnitedStates)
+                .strip_prefix(name.get(Locale::EnglishUnitedStates))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            spell.name = name.map(|text| format!("{text}{suffix}"));
         }
     }
 
@@ -108,6 +375,9 @@ impl PlannerCharacter {
     }
 
     pub fn toggle_spell_enabled(&mut self, spell: SpellUuid) {
+        if let Some(custom_spell) = self.custom_spells.get_mut(&spell) {
+            custom_spell.enabled = !custom_spell.enabled;
+        }
         let spell = self.spells.get_mut(&spell).unwrap();
         spell.enabled = !spell.enabled;
     }
@@ -136,6 +406,8 @@ impl LookupKey for PlannerCharacter {
 
 #[cfg(test)]
 mod tests {
+    use fight_domain::FromMinutesSeconds;
+
     use super::*;
 
     #[test]
@@ -147,9 +419,11 @@ mod tests {
                 slug: "test".to_string(),
             },
             class: LocalizedString::constant("Evoker"),
+            spec: LocalizedString::constant("Preservation"),
         };
 
         assert_eq!(template.class(), Some(&LocalizedString::constant("Evoker")));
+        assert_eq!(template.spec(), Some(&LocalizedString::constant("Preservation")));
 
         let uuid = CharacterUuid::new();
         let character = PlannerCharacter::new(uuid, template);
@@ -225,13 +499,14 @@ mod tests {
                 slug: "test".to_string(),
             },
             class: LocalizedString::constant("Evoker"),
+            spec: LocalizedString::constant("Preservation"),
         });
         character.change_spec(LocalizedString::constant("Preservation"));
 
         character.change_class(LocalizedString::constant("Druid"));
         assert_eq!(character.class, Some(LocalizedString::constant("Druid")));
-        assert_eq!(character.spec, None);
-        assert!(character.spells.is_empty());
+        assert_eq!(character.spec, Some(LocalizedString::constant("Restoration")));
+        assert!(character.spells.iter().any(|s| s.identifier == Identifier::Spell(740)));
         assert!(character.assignments.is_empty());
     }
 
@@ -244,11 +519,12 @@ mod tests {
                 slug: "test".to_string(),
             },
             class: LocalizedString::constant("Evoker"),
+            spec: LocalizedString::constant("Preservation"),
         });
         character.change_spec(LocalizedString::constant("Preservation"));
 
         character.change_class(LocalizedString::constant("Druid"));
-        assert!(character.spells.is_empty());
+        assert_eq!(character.spec, Some(LocalizedString::constant("Restoration")));
 
         character.change_spec(LocalizedString::constant("Restoration"));
         assert!(!character.spells.is_empty());
@@ -266,6 +542,7 @@ mod tests {
                 slug: "test".to_string(),
             },
             class: LocalizedString::constant("Evoker"),
+            spec: LocalizedString::constant("Preservation"),
         });
         character.change_spec(LocalizedString::constant("Devastation"));
         let spells_devastation = character.spells.len();
@@ -278,4 +555,291 @@ mod tests {
         assert_eq!(character.spec, Some(LocalizedString::constant("Preservation")));
         assert!(character.assignments.is_empty());
     }
+
+    fn racial() -> CustomSpellTemplate {
+        CustomSpellTemplate {
+            name: "Fireblood".to_string(),
+            icon_text: None,
+            identifier: fight_domain::Identifier::Spell(265221),
+            power: ordered_float::NotNan::new(0.5).unwrap(),
+            cooldown: fight_domain::TimeStep::mm_ss(2, 0),
+            cast_time: fight_domain::TimeStep::mm_ss(0, 1),
+            charges: 1,
+            exclusive_with: Default::default(),
+            minor: true,
+        }
+    }
+
+    fn restoration_druid() -> PlannerCharacter {
+        let mut character = PlannerCharacter::new(CharacterUuid::new(), PlannerCharacterTemplate::Custom {
+            name: "Test".to_string(),
+        });
+        character.change_class(LocalizedString::constant("Druid"));
+        character.change_spec(LocalizedString::constant("Restoration"));
+        character
+    }
+
+    #[test]
+    fn localize_spells_keeps_variant_suffix() {
+        let mut character = restoration_druid();
+        let tranquility = LocalizedString::new(
+            "宁静", "寧靜", "Tranquility", "Tranquility", "Tranquillité", "Gelassenheit",
+            "Tranquillità", "평온", "Tranquilidade", "Спокойствие", "Tranquilidad", "Tranquilidad",
+        );
+
+        character.localize_spells(&[(740, tranquility)].into());
+
+        let names = character
+            .spells
+            .iter()
+            .filter(|s| s.identifier == Identifier::Spell(740))
+            .map(|s| s.name.get(Locale::French).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Tranquillité 3m", "Tranquillité 2m"]);
+    }
+
+    #[test]
+    fn set_talents_enables_talented_variants() {
+        let mut character = restoration_druid();
+        let tranq_3m = character.spells.iter().find(|s| s.name == LocalizedString::constant("Tranquility 3m")).unwrap().uuid;
+        let tranq_2m = character.spells.iter().find(|s| s.name == LocalizedString::constant("Tranquility 2m")).unwrap().uuid;
+
+        character.set_talents([("Restoration".to_string(), [740, 197073].into())].into());
+
+        assert!(!character.spells.get(&tranq_3m).unwrap().enabled);
+        assert!(character.spells.get(&tranq_2m).unwrap().enabled);
+    }
+
+    #[test]
+    fn change_spec_applies_talents_of_new_spec() {
+        let mut character = restoration_druid();
+        character.set_talents([("Balance".to_string(), [106898].into())].into());
+
+        character.change_spec(LocalizedString::constant("Balance"));
+
+        assert!(character.spells.iter().find(|s| s.name == LocalizedString::constant("Stampeding Roar")).unwrap().enabled);
+    }
+
+    #[test]
+    fn plays_spec_with_talent_loadout() {
+        let mut character = restoration_druid();
+        character.set_talents([("Balance".to_string(), [106898].into())].into());
+
+        assert!(character.plays_spec(&LocalizedString::constant("Balance")));
+        assert!(!character.plays_spec(&LocalizedString::constant("Feral")));
+    }
+
+    #[test]
+    fn change_class_clears_talents() {
+        let mut character = restoration_druid();
+        character.set_talents([("Restoration".to_string(), [740].into())].into());
+
+        character.change_class(LocalizedString::constant("Shaman"));
+
+        assert!(character.talents.is_empty());
+    }
+
+    #[test]
+    fn custom_spells_are_added_after_spec_spells() {
+        let mut character = restoration_druid();
+        let spec_spells = character.spells.len();
+
+        let uuid = character.add_custom_spell(racial());
+
+        assert_eq!(character.spells.len(), spec_spells + 1);
+        assert_eq!(character.spells.iter().last().unwrap().uuid, uuid);
+        assert!(character.is_custom_spell(uuid));
+        assert!(character.spells.get(&uuid).unwrap().enabled);
+    }
+
+    #[test]
+    fn custom_spells_survive_spec_change() {
+        let mut character = restoration_druid();
+        let uuid = character.add_custom_spell(racial());
+
+        character.change_spec(LocalizedString::constant("Balance"));
+        assert!(character.spells.contains_key(&uuid));
+
+        character.change_class(LocalizedString::constant("Priest"));
+        assert_eq!(character.spells.iter().last().unwrap().uuid, uuid);
+    }
+
+    #[test]
+    fn change_spec_carries_over_assignments_of_shared_spells() {
+        let mut character = restoration_druid();
+        let racial = character.add_custom_spell(racial());
+        let find = |character: &PlannerCharacter, name: &str| character.spells.iter().find(|s| s.name == LocalizedString::constant(name)).unwrap().uuid;
+        let tranq_3m = find(&character, "Tranquility 3m");
+        let roar = find(&character, "Stampeding Roar");
+        let attack = AttackUuid::new(uuid::Uuid::new_v4());
+        for spell in [racial, tranq_3m, roar] {
+            character.assignments.assign_locked(spell, attack);
+        }
+
+        let dropped = character.change_spec(LocalizedString::constant("Balance"));
+
+        assert_eq!(dropped, [DroppedAssignment {
+            spell: tranq_3m,
+            spell_name: LocalizedString::constant("Tranquility 3m"),
+            attack,
+        }]);
+        let mut locked = character.assignments.locked().copied().collect::<Vec<_>>();
+        locked.sort();
+        let mut expected = vec![(racial, attack), (roar, attack)];
+        expected.sort();
+        assert_eq!(locked, expected);
+    }
+
+    #[test]
+    fn change_class_carries_over_to_the_default_spec() {
+        let mut character = restoration_druid();
+        let tranq_3m = character.spells.iter().find(|s| s.name == LocalizedString::constant("Tranquility 3m")).unwrap().uuid;
+        let attack = AttackUuid::new(uuid::Uuid::new_v4());
+        character.assignments.assign_locked(tranq_3m, attack);
+
+        let dropped = character.change_class(LocalizedString::constant("Druid"));
+
+        assert!(dropped.is_empty());
+        assert_eq!(character.spec, Some(LocalizedString::constant("Restoration")));
+        assert_eq!(character.assignments.locked().collect::<Vec<_>>(), [&(tranq_3m, attack)]);
+    }
+
+    #[test]
+    fn carry_over_assignments_uses_spell_with_same_identifier() {
+        let mut previous = restoration_druid();
+        let tranq_3m = previous.spells.iter().find(|s| s.name == LocalizedString::constant("Tranquility 3m")).unwrap().uuid;
+        let attack = AttackUuid::new(uuid::Uuid::new_v4());
+        previous.assignments.assign_locked(tranq_3m, attack);
+        let mut character = restoration_druid();
+        character.spells.take(&tranq_3m);
+        let tranq_2m = character.spells.iter().find(|s| s.name == LocalizedString::constant("Tranquility 2m")).unwrap().uuid;
+
+        let dropped = character.carry_over_assignments(&previous.spells, &previous.assignments);
+
+        assert!(dropped.is_empty());
+        assert_eq!(character.assignments.locked().collect::<Vec<_>>(), [&(tranq_2m, attack)]);
+    }
+
+    #[test]
+    fn update_custom_spell_keeps_enabled_state() {
+        let mut character = restoration_druid();
+        let uuid = character.add_custom_spell(racial());
+        character.toggle_spell_enabled(uuid);
+
+        let mut updated = racial();
+        updated.cooldown = fight_domain::TimeStep::mm_ss(1, 30);
+        character.update_custom_spell(uuid, updated);
+
+        let spell = character.spells.get(&uuid).unwrap();
+        assert_eq!(spell.cooldown, fight_domain::TimeStep::mm_ss(1, 30));
+        assert!(!spell.enabled);
+    }
+
+    #[test]
+    fn remove_custom_spell_removes_its_assignments() {
+        let mut character = restoration_druid();
+        let uuid = character.add_custom_spell(racial());
+        let attack = AttackUuid::new(uuid::Uuid::new_v4());
+        character.assignments.assign_locked(uuid, attack);
+
+        character.remove_custom_spell(uuid);
+
+        assert!(!character.spells.contains_key(&uuid));
+        assert!(character.assignments.is_empty());
+    }
+
+    #[test]
+    fn override_built_in_spell() {
+        let mut character = restoration_druid();
+        let spell = character.spells.iter().next().unwrap().clone();
+
+        character.override_spell(spell.uuid, SpellOverride {
+            cooldown: Some(fight_domain::TimeStep::mm_ss(1, 30)),
+            charges: Some(2),
+            ..Default::default()
+        });
+
+        let overridden = character.spells.get(&spell.uuid).unwrap();
+        assert_eq!(overridden.cooldown, fight_domain::TimeStep::mm_ss(1, 30));
+        assert_eq!(overridden.charges, 2);
+        assert_eq!(overridden.name, spell.name);
+        assert_eq!(overridden.identifier, spell.identifier);
+
+        character.reset_spell_override(spell.uuid);
+        assert_eq!(character.spells.get(&spell.uuid).unwrap(), &spell);
+    }
+
+    #[test]
+    fn change_spec_drops_overrides_of_spells_the_spec_does_not_have() {
+        let mut character = restoration_druid();
+        let find = |character: &PlannerCharacter, name: &str| character.spells.iter().find(|s| s.name == LocalizedString::constant(name)).unwrap().uuid;
+        let tranq_3m = find(&character, "Tranquility 3m");
+        let roar = find(&character, "Stampeding Roar");
+        for spell in [tranq_3m, roar] {
+            character.override_spell(spell, SpellOverride {
+                charges: Some(2),
+                ..Default::default()
+            });
+        }
+
+        character.change_spec(LocalizedString::constant("Balance"));
+
+        assert!(!character.spell_overrides.contains_key(&tranq_3m));
+        assert!(character.spell_overrides.contains_key(&roar));
+        assert_eq!(character.spells.get(&roar).unwrap().charges, 2);
+    }
+
+    #[test]
+    fn overrides_are_used_for_the_optimizer() {
+        let mut character = restoration_druid();
+        let spell = character.spells.iter().next().unwrap().clone();
+        character.override_spell(spell.uuid, SpellOverride {
+            power: Some(ordered_float::NotNan::new(3.0).unwrap()),
+            ..Default::default()
+        });
+
+        let character: Character = character.into();
+
+        assert_eq!(character.spells.get(&spell.uuid).unwrap().power.into_inner(), 3.0);
+    }
+
+    #[test]
+    fn saved_character_round_trip() {
+        let mut character = restoration_druid();
+        character.set_talents([("Restoration".to_string(), [740, 197073].into())].into());
+        let custom = character.add_custom_spell(racial());
+        let built_in = character.spells.iter().next().unwrap().uuid;
+        character.override_spell(built_in, SpellOverride {
+            charges: Some(2),
+            ..Default::default()
+        });
+        character.toggle_spell_enabled(built_in);
+        character.assignments.assign_locked(custom, AttackUuid::new(uuid::Uuid::new_v4()));
+
+        let restored = PlannerCharacter::from_saved(&character.to_saved());
+
+        assert_eq!(restored, character);
+    }
+
+    #[test]
+    fn saved_locked_assignments_are_sorted() {
+        let mut character = restoration_druid();
+        for spell in character.spells.iter().map(|spell| spell.uuid).collect::<Vec<_>>() {
+            for _ in 0..4 {
+                character.assignments.assign_locked(spell, AttackUuid::new(uuid::Uuid::new_v4()));
+            }
+        }
+
+        let saved = character.to_saved();
+
+        assert!(saved.locked_assignments.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn override_custom_spell_panics() {
+        let mut character = restoration_druid();
+        let uuid = character.add_custom_spell(racial());
+        character.override_spell(uuid, SpellOverride::default());
+    }
 }
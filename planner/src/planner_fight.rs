@@ -1,16 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
 use auto_battle_net::game_data::journal::journal_encounter::JournalEncounterResponse;
 use auto_battle_net::game_data::journal::journal_instance::JournalInstanceResponse;
-use fight_domain::{Attack, Lookup};
-use i18n::LocalizedString;
+use fight_domain::{Attack, Identifier, Lookup};
+use i18n::{Locale, LocalizedString};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Difficulty {
     Heroic,
+    #[default]
     Mythic,
 }
 
+impl Difficulty {
+    pub const ALL: [Difficulty; 2] = [Difficulty::Heroic, Difficulty::Mythic];
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Heroic => write!(f, "Heroic"),
+            Difficulty::Mythic => write!(f, "Mythic"),
+        }
+    }
+}
+
 pub trait PlannerFight: Send + Sync {
     fn data(&self) -> &Option<PlannerFightData>;
+    fn difficulty(&self) -> Difficulty;
     fn attacks(&self) -> Lookup<Attack>;
 }
 
@@ -24,6 +43,14 @@ pub struct PlannerFightData {
     pub difficulty: Difficulty,
     pub image_path: &'static str,
     pub image_offset: i32,
+    pub abilities: Vec<PlannerFightAbility>,
+}
+
+/// Boss spell listed in an encounter journal section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannerFightAbility {
+    pub name: LocalizedString,
+    pub spell_id: u32,
 }
 
 impl PlannerFightData {
@@ -43,6 +70,106 @@ impl PlannerFightData {
             difficulty,
             image_path,
             image_offset,
+            abilities: encounter_info
+                .sections
+                .iter()
+                .flat_map(|section| {
+                    section
+                        .spell
+                        .iter()
+                        .chain(section.sections.iter().filter_map(|section| section.spell.as_ref()))
+                })
+                .map(|spell| PlannerFightAbility {
+                    name: spell.name.clone(),
+                    spell_id: spell.id as u32,
+                })
+                .collect(),
         }
     }
+
+    /// Journal ability of the attack's spell identifier, of the spell its trigger casts, or named
+    /// like the attack.
+    pub fn ability(&self, attack: &Attack) -> Option<&PlannerFightAbility> {
+        let by_spell = |spell_id: u32| self.abilities.iter().find(|a| a.spell_id == spell_id);
+        let identifier = match attack.identifier {
+            Some(Identifier::Spell(spell_id)) => Some(spell_id),
+            _ => None,
+        };
+        let trigger = attack
+            .timer
+            .dynamic_trigger_cleu_event
+            .as_ref()
+            .and_then(|event| u32::try_from(event.event).ok());
+        identifier
+            .and_then(by_spell)
+            .or_else(|| trigger.and_then(by_spell))
+            .or_else(|| self.ability_named(&attack.name))
+    }
+
+    /// Journal ability with the same English name, which carries its translations.
+    pub fn ability_named(&self, name: &LocalizedString) -> Option<&PlannerFightAbility> {
+        self.abilities.iter().find(|a| {
+            a.name
+                .get(Locale::EnglishUnitedStates)
+                .eq_ignore_ascii_case(name.get(Locale::EnglishUnitedStates))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::{AttackTimer, AttackType, AttackUuid, CleuEvent, CleuEventType};
+    use ordered_float::NotNan;
+
+    use super::*;
+
+    fn attack(name: &str, trigger: Option<u64>) -> Attack {
+        Attack {
+            uuid: AttackUuid::random(),
+            name: LocalizedString::constant(name),
+            identifier: None,
+            power: NotNan::new(1.0).unwrap(),
+            r#type: AttackType::RaidDamage,
+            timer: AttackTimer {
+                dynamic_trigger_cleu_event: trigger.map(|event| CleuEvent {
+                    r#type: CleuEventType::SpellCastStart,
+                    event,
+                    counter: 1,
+                }),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn ability_by_identifier_trigger_or_name() {
+        let data = PlannerFightData {
+            instance_id: 0,
+            instance_name: LocalizedString::constant(""),
+            encounter_id: 0,
+            encounter_name: LocalizedString::constant(""),
+            encounter_description: LocalizedString::constant(""),
+            difficulty: Difficulty::Mythic,
+            image_path: "",
+            image_offset: 0,
+            abilities: vec![
+                PlannerFightAbility {
+                    name: LocalizedString::constant("Wings of Extinction"),
+                    spell_id: 401316,
+                },
+                PlannerFightAbility {
+                    name: LocalizedString::constant("Hellbeam"),
+                    spell_id: 400430,
+                },
+            ],
+        };
+
+        let spell_id = |attack: &Attack| data.ability(attack).map(|ability| ability.spell_id);
+        assert_eq!(spell_id(&attack("80% HP AoE", Some(401316))), Some(401316));
+        assert_eq!(spell_id(&attack("hellbeam", None)), Some(400430));
+        assert_eq!(spell_id(&attack("Knock AoE", None)), None);
+        let mut identified = attack("Knock AoE", Some(401316));
+        identified.identifier = Some(Identifier::Spell(400430));
+        assert_eq!(spell_id(&identified), Some(400430));
+    }
 }
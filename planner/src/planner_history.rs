@@ -0,0 +1,86 @@
+use fight_domain::{Attack, Lookup};
+
+use crate::{Difficulty, PlannerCharacter};
+
+const MAX_HISTORY: usize = 100;
+
+/// An edit of the plan by the user, the entries of the undo history.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PlannerEdit {
+    SelectFight,
+    SelectDifficulty,
+    AddFight,
+    AddCharacter,
+    ReplaceCharacter,
+    RemoveCharacter,
+    ChangeClass,
+    ChangeSpec,
+    AddCustomAttack,
+    UpdateCustomAttack,
+    RemoveCustomAttack,
+    ApplyAttackDamage,
+    ToggleAssignment,
+    LockSuggestions,
+    ToggleSpell,
+    SetTalents,
+    AddCustomSpell,
+    UpdateCustomSpell,
+    RemoveCustomSpell,
+    OverrideSpell,
+    ResetSpellOverride,
+    ImportNote,
+    ImportSheet,
+}
+
+/// The parts of the planner state edits change.
+#[derive(Debug, Clone)]
+pub(crate) struct PlanSnapshot {
+    pub selected_difficulty: Difficulty,
+    pub selected_fight_index: usize,
+    pub custom_attacks: Lookup<Attack>,
+    pub characters: Lookup<PlannerCharacter>,
+}
+
+/// The state before each edit, and after each undone edit.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PlannerHistory {
+    undo: Vec<(PlannerEdit, PlanSnapshot)>,
+    redo: Vec<(PlannerEdit, PlanSnapshot)>,
+    /// Set while an edit runs, edits made by it are part of it.
+    pub editing: bool,
+}
+
+impl PlannerHistory {
+    pub fn push(&mut self, edit: PlannerEdit, before: PlanSnapshot) {
+        if self.undo.len() == MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.undo.push((edit, before));
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, current: PlanSnapshot) -> Option<(PlannerEdit, PlanSnapshot)> {
+        let (edit, before) = self.undo.pop()?;
+        self.redo.push((edit, current));
+        Some((edit, before))
+    }
+
+    pub fn redo(&mut self, current: PlanSnapshot) -> Option<(PlannerEdit, PlanSnapshot)> {
+        let (edit, after) = self.redo.pop()?;
+        self.undo.push((edit, current));
+        Some((edit, after))
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn next_undo(&self) -> Option<PlannerEdit> {
+        self.undo.last().map(|(edit, _)| *edit)
+    }
+
+    pub fn next_redo(&self) -> Option<PlannerEdit> {
+        self.redo.last().map(|(edit, _)| *edit)
+    }
+}
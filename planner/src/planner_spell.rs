@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use ordered_float::NotNan;
+
+use fight_domain::{Identifier, Spell, SpellUuid, TimeStep};
+use i18n::{Locale, LocalizedString};
+
+/// A user-defined spell added to a character next to the spells of their spec.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CustomSpellTemplate {
+    pub name: String,
+    pub icon_text: Option<String>,
+    pub identifier: Identifier,
+    pub power: NotNan<f64>,
+    pub cooldown: TimeStep,
+    pub cast_time: TimeStep,
+    pub charges: usize,
+    pub exclusive_with: BTreeSet<Identifier>,
+    pub minor: bool,
+}
+
+impl CustomSpellTemplate {
+    pub fn into_spell(self, uuid: SpellUuid, enabled: bool) -> Spell {
+        Spell {
+            uuid,
+            name: LocalizedString::constant(&self.name),
+            icon_text: self.icon_text,
+            power: self.power,
+            cooldown: self.cooldown,
+            cast_time: self.cast_time,
+            identifier: self.identifier,
+            charges: self.charges,
+            exclusive_with: self.exclusive_with,
+            enabled,
+            minor: self.minor,
+        }
+    }
+}
+
+impl From<&Spell> for CustomSpellTemplate {
+    fn from(spell: &Spell) -> Self {
+        Self {
+            name: spell.name.get(Locale::EnglishUnitedStates).to_owned(),
+            icon_text: spell.icon_text.clone(),
+            identifier: spell.identifier.clone(),
+            power: spell.power,
+            cooldown: spell.cooldown,
+            cast_time: spell.cast_time,
+            charges: spell.charges,
+            exclusive_with: spell.exclusive_with.clone(),
+            minor: spell.minor,
+        }
+    }
+}
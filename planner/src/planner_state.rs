@@ -1,19 +1,49 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 
 use itertools::Itertools;
 
-use fight_domain::{Attack, AttackUuid, CharacterUuid, Lookup, SpellUuid, TimeStep};
+use fight_domain::{
+    Attack, AttackUuid, CharacterUuid, FromMinutesSeconds, Identifier, Lookup, SpellOverride,
+    SpellUuid, TimeStep,
+};
 use i18n::{Locale, LocalizedString};
 use optimizer::{Assignment, AssignmentState};
 
-use crate::{AsInGameNote, PlannerCharacter, PlannerCharacterTemplate, PlannerFight};
+use crate::combat_log::{
+    verify_casts, AttackDamage, CastVerification, CombatLogEncounter, PlannedCast,
+};
+use crate::discord_message::discord_messages;
+use crate::note_import::{note_name_matches, parse_note};
+use crate::planner_history::{PlanSnapshot, PlannerHistory};
+use crate::share_code::{ShareCodeReader, ShareCodeWriter};
+use crate::spreadsheet::{
+    Sheet, SheetCellState, SheetImportProblem, SheetImportReport, ATTACK_COLUMNS, ATTACK_HEADER,
+    LOCKED_CELL, SUGGESTED_CELL, TIME_HEADER, TYPE_HEADER,
+};
+use crate::weak_aura::{reminders_weak_aura, WeakAuraReminder};
+use crate::{
+    AsInGameNote, CustomAttackTemplate, CustomSpellTemplate, Difficulty, DroppedAssignment,
+    NoteImportProblem, NoteImportReport, PlannerCharacter, PlannerCharacterTemplate, PlannerEdit,
+    PlannerFight, PlannerRealm, SavedCharacter, SavedFight, SavedPlan, ShareCodeError,
+    EVERYONE_TAG,
+};
+
+/// Looks up the saved plan of a fight when it gets selected, e.g. in the browser storage.
+pub type FightPlanLoader = Arc<dyn Fn(&SavedFight) -> Option<SavedPlan> + Send + Sync>;
 
 #[derive(Clone)]
 pub struct PlannerState {
     fights: Vec<Arc<dyn PlannerFight>>,
+    selected_difficulty: Difficulty,
     selected_fight_index: usize,
+    custom_attacks: Lookup<Attack>,
     characters: Lookup<PlannerCharacter>,
+    locale: Locale,
+    spell_names: HashMap<u32, LocalizedString>,
+    fight_plan_loader: Option<FightPlanLoader>,
+    history: PlannerHistory,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -28,52 +58,173 @@ impl PlannerState {
     pub fn new(fights: Vec<Arc<dyn PlannerFight>>) -> Self {
         Self {
             fights,
+            selected_difficulty: Difficulty::default(),
             selected_fight_index: 0,
+            custom_attacks: Lookup::default(),
             characters: [PlannerCharacter::new(
                 CharacterUuid::general(),
                 PlannerCharacterTemplate::General,
             )]
             .into_iter()
             .collect(),
+            locale: Locale::EnglishUnitedStates,
+            spell_names: HashMap::new(),
+            fight_plan_loader: None,
+            history: PlannerHistory::default(),
         }
     }
 
-    pub fn add_character(&mut self, new_character: PlannerCharacterTemplate) -> CharacterUuid {
-        if matches!(new_character, PlannerCharacterTemplate::General) {
-            panic!("Cannot add general character");
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Locale of the displayed and exported attack and spell names.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+
+    /// Spell ids of every spell the characters can use, to fetch their translated names.
+    pub fn spell_ids(&self) -> BTreeSet<u32> {
+        self.characters
+            .iter()
+            .flat_map(|character| character.spells.iter())
+            .filter_map(|spell| match spell.identifier {
+                Identifier::Spell(id) => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn set_spell_names(&mut self, names: HashMap<u32, LocalizedString>) {
+        self.spell_names.extend(names);
+        for character in self.characters.iter_mut() {
+            character.localize_spells(&self.spell_names);
         }
-        let uuid = CharacterUuid::new();
-        // keep general character last
-        self.characters.insert(
-            self.characters.len() - 1,
-            PlannerCharacter::new(uuid, new_character),
-        );
-        uuid
     }
 
+    pub fn add_character(&mut self, new_character: PlannerCharacterTemplate) -> CharacterUuid {
+        self.edit(PlannerEdit::AddCharacter, |state| {
+            if matches!(new_character, PlannerCharacterTemplate::General) {
+                panic!("Cannot add general character");
+            }
+            let uuid = CharacterUuid::new();
+            let mut character = PlannerCharacter::new(uuid, new_character);
+            character.localize_spells(&state.spell_names);
+            // keep general character last
+            state
+                .characters
+                .insert(state.characters.len() - 1, character);
+            uuid
+        })
+    }
+
+    /// Replaces the character with a new one, e.g. a placeholder with the actual player, keeping
+    /// the assignments of spells the new character has too.
     pub fn replace_character(
         &mut self,
         prev_uuid: CharacterUuid,
         new_character: PlannerCharacterTemplate,
-    ) -> CharacterUuid {
-        if prev_uuid == CharacterUuid::general() {
-            panic!("Cannot replace general character");
-        } else if matches!(new_character, PlannerCharacterTemplate::General) {
-            panic!("Cannot replace with new general character");
+    ) -> (CharacterUuid, Vec<DroppedAssignment>) {
+        self.edit(PlannerEdit::ReplaceCharacter, |state| {
+            if prev_uuid == CharacterUuid::general() {
+                panic!("Cannot replace general character");
+            } else if matches!(new_character, PlannerCharacterTemplate::General) {
+                panic!("Cannot replace with new general character");
+            }
+            let uuid = CharacterUuid::new();
+            let mut character = PlannerCharacter::new(uuid, new_character);
+            let previous = state.characters.get(&prev_uuid).unwrap();
+            let mut dropped = character.carry_over_assignments(&previous.spells, &previous.assignments);
+            character.localize_spells(&state.spell_names);
+            state.characters.replace(&prev_uuid, character);
+            dropped.extend(state.drop_unassignable_assignments(uuid));
+            (uuid, dropped)
+        })
+    }
+
+    /// Checks the locked assignments carried over to new spells in timeline order, the new spells
+    /// can have a longer cooldown, fewer charges or be exclusive with each other.
+    fn drop_unassignable_assignments(&mut self, uuid: CharacterUuid) -> Vec<DroppedAssignment> {
+        let attacks = self.attacks();
+        let character = self.characters.get_mut(&uuid).unwrap();
+        let assignments = std::mem::take(&mut character.assignments);
+        let locked = assignments
+            .locked()
+            .copied()
+            .sorted_by_key(|(_, attack)| attacks.get(attack).map(|attack| attack.timer.static_timer()))
+            .collect::<Vec<_>>();
+
+        let mut dropped = vec![];
+        for (spell, attack) in locked {
+            let assignable = !attacks.contains_key(&attack)
+                || self.is_spell_assignable(uuid, spell, attack) == Assignability::Assignable;
+            let character = self.characters.get_mut(&uuid).unwrap();
+            if assignable {
+                character.assignments.assign_locked(spell, attack);
+            } else {
+                dropped.push(DroppedAssignment {
+                    spell,
+                    spell_name: character.spells.get(&spell).unwrap().name.clone(),
+                    attack,
+                });
+            }
         }
-        let uuid = CharacterUuid::new();
-        let character = PlannerCharacter::new(uuid, new_character);
-        self.characters.replace(&prev_uuid, character);
-        uuid
+        dropped
     }
 
     pub fn remove_character(&mut self, uuid: CharacterUuid) {
-        if uuid == CharacterUuid::general() {
-            panic!("Cannot remove general character");
-        }
-        self.characters.take(&uuid);
+        self.edit(PlannerEdit::RemoveCharacter, |state| {
+            if uuid == CharacterUuid::general() {
+                panic!("Cannot remove general character");
+            }
+            state.characters.take(&uuid);
+        })
+    }
+
+    pub fn add_custom_attack(&mut self, attack: CustomAttackTemplate) -> AttackUuid {
+        self.edit(PlannerEdit::AddCustomAttack, |state| {
+            let uuid = AttackUuid::random();
+            state.custom_attacks.put(attack.into_attack(uuid));
+            uuid
+        })
+    }
+
+    pub fn update_custom_attack(&mut self, uuid: AttackUuid, attack: CustomAttackTemplate) {
+        self.edit(PlannerEdit::UpdateCustomAttack, |state| {
+            if !state.custom_attacks.contains_key(&uuid) {
+                panic!("Cannot update an attack that is not a custom attack");
+            }
+            state.custom_attacks.put(attack.into_attack(uuid));
+        })
+    }
+
+    pub fn remove_custom_attack(&mut self, uuid: AttackUuid) {
+        self.edit(PlannerEdit::RemoveCustomAttack, |state| {
+            if state.custom_attacks.take(&uuid).is_some() {
+                for character in state.characters.iter_mut() {
+                    character.assignments.remove_attack(uuid);
+                }
+            }
+        })
+    }
+
+    /// Takes power and type of the custom attacks from the damage their spell did in combat logs.
+    pub fn apply_attack_damage(&mut self, damage: &[AttackDamage]) {
+        self.edit(PlannerEdit::ApplyAttackDamage, |state| {
+            for attack in state.custom_attacks.iter_mut() {
+                let Some(Identifier::Spell(spell_id)) = attack.identifier else {
+                    continue;
+                };
+                if let Some(damage) = damage.iter().find(|damage| damage.spell_id == spell_id) {
+                    attack.power = damage.power;
+                    attack.r#type = damage.r#type;
+                }
+            }
+        })
     }
 
+    /// Suggestions are derived from the plan by the optimizer, so they are not an edit and are
+    /// kept by undo and redo until the optimizer has replaced them for the restored plan.
     pub fn replace_assignment_suggestions(&mut self, assignments: Lookup<Assignment>) {
         for character in self.characters.iter_mut() {
             character.assignments.replace_suggestions(
@@ -93,63 +244,517 @@ impl PlannerState {
         spell: SpellUuid,
         attack: AttackUuid,
     ) {
-        let state = self
-            .characters
-            .get(&character)
-            .unwrap()
-            .assignment_state(spell, attack);
+        self.edit(PlannerEdit::ToggleAssignment, |state| {
+            let assignment_state = state
+                .characters
+                .get(&character)
+                .unwrap()
+                .assignment_state(spell, attack);
+
+            if assignment_state == AssignmentState::Locked {
+                // remove currently locked assignment
+                let character = state.characters.get_mut(&character).unwrap();
+                character.assignments.unlock(spell, attack);
+            } else {
+                // need to check if the spell is actually assignable
+                let is_spell_assignable = state.is_spell_assignable(character, spell, attack);
+                if matches!(is_spell_assignable, Assignability::Assignable) {
+                    // create new locked assignment
+                    let character = state.characters.get_mut(&character).unwrap();
+                    character.assignments.assign_locked(spell, attack);
+                }
+            }
+        })
+    }
 
-        if state == AssignmentState::Locked {
-            // remove currently locked assignment
-            let character = self.characters.get_mut(&character).unwrap();
-            character.assignments.unlock(spell, attack);
-        } else {
-            // need to check if the spell is actually assignable
-            let is_spell_assignable = self.is_spell_assignable(character, spell, attack);
-            if matches!(is_spell_assignable, Assignability::Assignable) {
-                // create new locked assignment
-                let character = self.characters.get_mut(&character).unwrap();
-                character.assignments.assign_locked(spell, attack);
+    pub fn lock_suggestions(&mut self) {
+        self.edit(PlannerEdit::LockSuggestions, |state| {
+            for character in state.characters.iter_mut() {
+                character.assignments.lock_suggestions();
+            }
+        })
+    }
+
+    /// Returns the assignments the new spells of the character could not take over.
+    pub fn change_character_class(
+        &mut self,
+        uuid: CharacterUuid,
+        class: LocalizedString,
+    ) -> Vec<DroppedAssignment> {
+        self.edit(PlannerEdit::ChangeClass, |state| {
+            if uuid == CharacterUuid::general() {
+                panic!("Cannot change the class of the general character");
+            }
+            let character = state.characters.get_mut(&uuid).unwrap();
+            if Some(&class) == character.class.as_ref() {
+                return vec![];
+            }
+            let mut dropped = character.change_class(class);
+            character.localize_spells(&state.spell_names);
+            dropped.extend(state.drop_unassignable_assignments(uuid));
+            dropped
+        })
+    }
+
+    /// Returns the assignments the new spells of the character could not take over.
+    pub fn change_character_spec(
+        &mut self,
+        uuid: CharacterUuid,
+        spec: LocalizedString,
+    ) -> Vec<DroppedAssignment> {
+        self.edit(PlannerEdit::ChangeSpec, |state| {
+            if uuid == CharacterUuid::general() {
+                panic!("Cannot change the spec of the general character");
+            }
+            let character = state.characters.get_mut(&uuid).unwrap();
+            if Some(&spec) == character.spec.as_ref() {
+                return vec![];
             }
+            let mut dropped = character.change_spec(spec);
+            character.localize_spells(&state.spell_names);
+            dropped.extend(state.drop_unassignable_assignments(uuid));
+            dropped
+        })
+    }
+
+    pub fn set_character_talents(
+        &mut self,
+        character: CharacterUuid,
+        talents: HashMap<String, BTreeSet<u32>>,
+    ) {
+        self.edit(PlannerEdit::SetTalents, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.set_talents(talents);
+        })
+    }
+
+    pub fn toggle_spell_enabled(&mut self, character: CharacterUuid, spell: SpellUuid) {
+        self.edit(PlannerEdit::ToggleSpell, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.toggle_spell_enabled(spell);
+        })
+    }
+
+    pub fn add_custom_spell(
+        &mut self,
+        character: CharacterUuid,
+        spell: CustomSpellTemplate,
+    ) -> SpellUuid {
+        self.edit(PlannerEdit::AddCustomSpell, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.add_custom_spell(spell)
+        })
+    }
+
+    pub fn update_custom_spell(
+        &mut self,
+        character: CharacterUuid,
+        uuid: SpellUuid,
+        spell: CustomSpellTemplate,
+    ) {
+        self.edit(PlannerEdit::UpdateCustomSpell, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.update_custom_spell(uuid, spell);
+        })
+    }
+
+    pub fn remove_custom_spell(&mut self, character: CharacterUuid, spell: SpellUuid) {
+        self.edit(PlannerEdit::RemoveCustomSpell, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.remove_custom_spell(spell);
+        })
+    }
+
+    pub fn override_spell(
+        &mut self,
+        character: CharacterUuid,
+        spell: SpellUuid,
+        spell_override: SpellOverride,
+    ) {
+        self.edit(PlannerEdit::OverrideSpell, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.override_spell(spell, spell_override);
+        })
+    }
+
+    pub fn reset_spell_override(&mut self, character: CharacterUuid, spell: SpellUuid) {
+        self.edit(PlannerEdit::ResetSpellOverride, |state| {
+            let character = state.characters.get_mut(&character).unwrap();
+            character.reset_spell_override(spell);
+        })
+    }
+
+    /// Reverts the last edit, loading a plan is not an edit.
+    pub fn undo(&mut self) -> Option<PlannerEdit> {
+        let (edit, before) = self.history.undo(self.snapshot())?;
+        self.restore(before);
+        Some(edit)
+    }
+
+    pub fn redo(&mut self) -> Option<PlannerEdit> {
+        let (edit, after) = self.history.redo(self.snapshot())?;
+        self.restore(after);
+        Some(edit)
+    }
+
+    /// The edit [`Self::undo`] would revert.
+    pub fn next_undo(&self) -> Option<PlannerEdit> {
+        self.history.next_undo()
+    }
+
+    pub fn next_redo(&self) -> Option<PlannerEdit> {
+        self.history.next_redo()
+    }
+
+    /// Runs an edit, remembering the state before it if it changed anything.
+    fn edit<T>(&mut self, edit: PlannerEdit, f: impl FnOnce(&mut Self) -> T) -> T {
+        if self.history.editing {
+            return f(self);
+        }
+        let before = self.snapshot();
+        self.history.editing = true;
+        // reset even when the edit panics, or no edit would be recorded afterwards
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(self)));
+        self.history.editing = false;
+        let result = result.unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        if before.selected_difficulty != self.selected_difficulty
+            || before.selected_fight_index != self.selected_fight_index
+            || before.custom_attacks != self.custom_attacks
+            || before.characters != self.characters
+        {
+            self.history.push(edit, before);
         }
+        result
     }
 
-    pub fn lock_suggestions(&mut self) {
+    fn snapshot(&self) -> PlanSnapshot {
+        PlanSnapshot {
+            selected_difficulty: self.selected_difficulty,
+            selected_fight_index: self.selected_fight_index,
+            custom_attacks: self.custom_attacks.clone(),
+            characters: self.characters.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: PlanSnapshot) {
+        let suggestions = self
+            .characters
+            .iter()
+            .flat_map(|character| {
+                character
+                    .assignments
+                    .suggested()
+                    .map(move |&(spell, attack)| Assignment {
+                        character: character.uuid,
+                        spell,
+                        attack,
+                        state: AssignmentState::Suggested,
+                    })
+            })
+            .collect();
+        self.selected_difficulty = snapshot.selected_difficulty;
+        self.selected_fight_index = snapshot.selected_fight_index;
+        self.custom_attacks = snapshot.custom_attacks;
+        self.characters = snapshot.characters;
+        // the snapshot holds the suggestions of its time, the optimizer may have replaced them since
+        self.replace_assignment_suggestions(suggestions);
+        // spell names may have been fetched since
         for character in self.characters.iter_mut() {
-            character.assignments.lock_suggestions();
+            character.localize_spells(&self.spell_names);
         }
     }
 
-    pub fn change_character_class(&mut self, uuid: CharacterUuid, class: LocalizedString) {
-        if uuid == CharacterUuid::general() {
-            panic!("Cannot change the class of the general character");
+    pub fn saved_fight(&self) -> SavedFight {
+        let data = self.selected_fight().and_then(|fight| fight.data().clone());
+        SavedFight {
+            encounter_id: data.as_ref().map(|data| data.encounter_id).unwrap_or_default(),
+            name: data
+                .map(|data| data.encounter_name.get(Locale::EnglishUnitedStates).to_owned())
+                .unwrap_or_default(),
+            difficulty: self.selected_difficulty,
         }
-        let character = self.characters.get_mut(&uuid).unwrap();
-        if Some(&class) != character.class.as_ref() {
-            character.change_class(class);
+    }
+
+    pub fn saved_plan(&self) -> SavedPlan {
+        SavedPlan {
+            fight: self.saved_fight(),
+            custom_attacks: self.custom_attacks.iter().cloned().collect(),
+            characters: self.characters.iter().map(PlannerCharacter::to_saved).collect(),
         }
     }
 
-    pub fn change_character_spec(&mut self, uuid: CharacterUuid, spec: LocalizedString) {
-        if uuid == CharacterUuid::general() {
-            panic!("Cannot change the spec of the general character");
+    /// Replaces the plan with a saved one. If its fight is not available, e.g. a fight built from
+    /// a note in another session, the first fight is selected and assignments to attacks it does
+    /// not have are dropped.
+    pub fn load_saved_plan(&mut self, plan: &SavedPlan) {
+        self.selected_difficulty = plan.fight.difficulty;
+        self.selected_fight_index = self
+            .fights()
+            .iter()
+            .position(|fight| {
+                fight.data().as_ref().is_some_and(|data| {
+                    data.encounter_id == plan.fight.encounter_id
+                        && (data.encounter_id != 0
+                            || data.encounter_name.get(Locale::EnglishUnitedStates)
+                                == plan.fight.name)
+                })
+            })
+            .unwrap_or_default();
+        self.load_fight_plan(plan);
+        // edits of the previous plan do not apply to this one
+        self.history.clear();
+    }
+
+    /// Takes over the roster and custom attacks of a plan for the selected fight.
+    fn load_fight_plan(&mut self, plan: &SavedPlan) {
+        self.custom_attacks = plan.custom_attacks.iter().cloned().collect();
+        self.characters = plan
+            .characters
+            .iter()
+            .map(PlannerCharacter::from_saved)
+            .collect();
+        if !self.characters.contains_key(&CharacterUuid::general()) {
+            self.characters.put(PlannerCharacter::new(
+                CharacterUuid::general(),
+                PlannerCharacterTemplate::General,
+            ));
         }
-        let character = self.characters.get_mut(&uuid).unwrap();
-        if Some(&spec) != character.spec.as_ref() {
-            character.change_spec(spec);
+
+        self.remove_assignments_to_missing_attacks();
+        let names = std::mem::take(&mut self.spell_names);
+        self.set_spell_names(names);
+    }
+
+    /// Assignments to attacks the selected fight does not have, e.g. after switching fights.
+    fn remove_assignments_to_missing_attacks(&mut self) {
+        let attacks = self.attacks();
+        for character in self.characters.iter_mut() {
+            for attack in character
+                .assignments
+                .all()
+                .map(|(_, attack)| *attack)
+                .filter(|attack| !attacks.contains_key(attack))
+                .collect::<Vec<_>>()
+            {
+                character.assignments.remove_attack(attack);
+            }
         }
     }
 
-    pub fn toggle_spell_enabled(&mut self, character: CharacterUuid, spell: SpellUuid) {
-        let character = self.characters.get_mut(&character).unwrap();
-        character.toggle_spell_enabled(spell);
+    /// The plan packed for a share link. Spells and attacks are referenced by their position and
+    /// talents are left out, the general character comes last like in the planner.
+    pub fn share_code(&self) -> String {
+        let plan = self.saved_plan();
+        let attacks = self.attacks();
+        let mut writer = ShareCodeWriter::new();
+        writer.number(plan.fight.encounter_id as u64);
+        writer.string(&plan.fight.name);
+        writer.number(
+            Difficulty::ALL
+                .iter()
+                .position(|difficulty| *difficulty == plan.fight.difficulty)
+                .unwrap() as u64,
+        );
+        writer.json(&plan.custom_attacks);
+
+        writer.number(self.characters.len() as u64);
+        for (character, saved) in self.characters.iter().zip(&plan.characters) {
+            let spell_index = |uuid: &SpellUuid| {
+                character
+                    .spells
+                    .iter()
+                    .position(|spell| spell.uuid == *uuid)
+            };
+            let localized = |string: &Option<LocalizedString>| {
+                string
+                    .as_ref()
+                    .map(|string| string.get(Locale::EnglishUnitedStates).to_owned())
+                    .unwrap_or_default()
+            };
+            writer.string(saved.name.as_deref().unwrap_or_default());
+            writer.string(&localized(
+                &saved.realm.as_ref().map(|realm| realm.name.clone()),
+            ));
+            writer.string(
+                saved
+                    .realm
+                    .as_ref()
+                    .map(|realm| realm.slug.as_str())
+                    .unwrap_or_default(),
+            );
+            writer.string(&localized(&saved.class));
+            writer.string(&localized(&saved.spec));
+            writer.json(&saved.custom_spells);
+            let overrides = saved
+                .spell_overrides
+                .iter()
+                .filter_map(|(uuid, spell_override)| Some((spell_index(uuid)?, spell_override)))
+                .sorted_by_key(|(index, _)| *index)
+                .collect::<Vec<_>>();
+            writer.json(&overrides);
+            writer.flags(character.spells.iter().map(|spell| spell.enabled));
+            let assignments = saved
+                .locked_assignments
+                .iter()
+                .filter_map(|(spell, attack)| {
+                    Some((
+                        spell_index(spell)?,
+                        attacks.iter().position(|a| a.uuid == *attack)?,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            writer.number(assignments.len() as u64);
+            for (spell, attack) in assignments {
+                writer.number(spell as u64);
+                writer.number(attack as u64);
+            }
+        }
+        writer.finish()
+    }
+
+    /// Replaces the plan with one from a share link, characters get new uuids. The plan is left
+    /// as it was when the code is invalid.
+    pub fn load_share_code(&mut self, code: &str) -> Result<(), ShareCodeError> {
+        let mut reader = ShareCodeReader::new(code)?;
+        let non_empty = |string: String| Some(string).filter(|string| !string.is_empty());
+        let mut plan = SavedPlan {
+            fight: SavedFight {
+                encounter_id: reader.number()? as i64,
+                name: reader.string()?,
+                difficulty: Difficulty::ALL[reader.index(Difficulty::ALL.len())?],
+            },
+            custom_attacks: reader.json()?,
+            characters: vec![],
+        };
+        // the attacks of the fight are needed to resolve the assignments
+        let mut state = self.clone();
+        state.load_saved_plan(&plan);
+        let attacks = state
+            .attacks()
+            .iter()
+            .map(|attack| attack.uuid)
+            .collect::<Vec<_>>();
+
+        let count = reader.number()?;
+        for index in 0..count {
+            let name = non_empty(reader.string()?);
+            let realm_name = reader.string()?;
+            let realm_slug = reader.string()?;
+            let mut saved = SavedCharacter {
+                uuid: if index + 1 == count {
+                    CharacterUuid::general()
+                } else {
+                    CharacterUuid::new()
+                },
+                name,
+                realm: non_empty(realm_slug).map(|slug| PlannerRealm {
+                    name: LocalizedString::constant(&realm_name),
+                    slug,
+                }),
+                class: non_empty(reader.string()?).map(|class| LocalizedString::constant(&class)),
+                spec: non_empty(reader.string()?).map(|spec| LocalizedString::constant(&spec)),
+                enabled_spells: BTreeSet::new(),
+                custom_spells: reader.json()?,
+                spell_overrides: BTreeMap::new(),
+                talents: BTreeMap::new(),
+                locked_assignments: vec![],
+            };
+            let spells = PlannerCharacter::from_saved(&saved)
+                .spells
+                .iter()
+                .map(|spell| spell.uuid)
+                .collect::<Vec<_>>();
+            let overrides: Vec<(usize, SpellOverride)> = reader.json()?;
+            for (spell, spell_override) in overrides {
+                let spell = *spells.get(spell).ok_or(ShareCodeError::Invalid)?;
+                saved.spell_overrides.insert(spell, spell_override);
+            }
+            saved.enabled_spells = reader
+                .flags(spells.len())?
+                .into_iter()
+                .zip(&spells)
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, spell)| *spell)
+                .collect();
+            for _ in 0..reader.number()? {
+                let spell = spells[reader.index(spells.len())?];
+                let attack = attacks[reader.index(attacks.len())?];
+                saved.locked_assignments.push((spell, attack));
+            }
+            plan.characters.push(saved);
+        }
+
+        // clears the history, the edits of the previous plan do not apply to this one
+        state.load_saved_plan(&plan);
+        *self = state;
+        Ok(())
     }
 
     pub fn set_selected_fight_index(&mut self, index: usize) {
-        if index >= self.fights.len() {
-            panic!("selected fight index out of bounds");
+        self.edit(PlannerEdit::SelectFight, |state| {
+            if index >= state.fights().len() {
+                panic!("selected fight index out of bounds");
+            }
+            if state.selected_fight_index != index {
+                state.selected_fight_index = index;
+                state.selected_fight_changed();
+            }
+        })
+    }
+
+    /// Replaces the fights the state was created with, e.g. once their journal data is loaded.
+    /// Fights added later stay.
+    pub fn replace_fights(&mut self, fights: Vec<Arc<dyn PlannerFight>>) {
+        if fights.len() > self.fights.len() {
+            panic!("More fights than the state was created with");
+        }
+        self.fights.splice(0..fights.len(), fights);
+    }
+
+    /// Adds a fight, e.g. one built from a note, and selects it.
+    pub fn add_fight(&mut self, fight: Arc<dyn PlannerFight>) {
+        self.edit(PlannerEdit::AddFight, |state| {
+            state.selected_difficulty = fight.difficulty();
+            state.fights.push(fight);
+            state.selected_fight_index = state.fights().len() - 1;
+            state.selected_fight_changed();
+        })
+    }
+
+    pub fn set_selected_difficulty(&mut self, difficulty: Difficulty) {
+        self.edit(PlannerEdit::SelectDifficulty, |state| {
+            if state.selected_difficulty == difficulty {
+                return;
+            }
+            state.selected_difficulty = difficulty;
+            // fights are listed in the same order for every difficulty, so the index usually
+            // carries over
+            if state.selected_fight_index >= state.fights().len() {
+                state.selected_fight_index = 0;
+            }
+            state.selected_fight_changed();
+        })
+    }
+
+    /// Sets where the saved plans of fights come from, they are loaded as part of selecting a fight
+    /// so that undoing the selection brings back the previous plan.
+    pub fn set_fight_plan_loader(&mut self, loader: FightPlanLoader) {
+        self.fight_plan_loader = Some(loader);
+    }
+
+    /// The saved plan of the newly selected fight replaces the current one. Without one, custom
+    /// attacks belong to the fight they were added to, they go along with the assignments to
+    /// attacks the newly selected fight does not have.
+    fn selected_fight_changed(&mut self) {
+        let fight = self.saved_fight();
+        let plan = self.fight_plan_loader.as_ref().and_then(|loader| loader(&fight));
+        match plan {
+            Some(plan) => self.load_fight_plan(&plan),
+            None => {
+                self.custom_attacks = Lookup::default();
+                self.remove_assignments_to_missing_attacks();
+            }
         }
-        self.selected_fight_index = index;
     }
 
     pub fn is_spell_assignable(
@@ -216,19 +821,55 @@ impl PlannerState {
         Assignability::Assignable
     }
 
+    /// Attacks of the selected fight merged with the custom attacks, in timeline order.
     pub fn attacks(&self) -> Lookup<Attack> {
-        self.fights
-            .get(self.selected_fight_index)
+        let selected_fight = self.selected_fight();
+        let fight_data = selected_fight.as_ref().and_then(|f| f.data().as_ref());
+        selected_fight
+            .as_ref()
             .map(|f| f.attacks())
             .unwrap_or_default()
+            .into_iter()
+            .chain(self.custom_attacks.iter().cloned())
+            .map(|mut attack| {
+                // the journal carries the translations of the attack names
+                if let Some(ability) = fight_data.and_then(|data| data.ability(&attack)) {
+                    attack.identifier.get_or_insert(Identifier::Spell(ability.spell_id));
+                    attack.name = ability.name.clone();
+                }
+                attack
+            })
+            .sorted_by_key(|attack| attack.timer.static_timer())
+            .collect()
+    }
+
+    pub fn custom_attacks(&self) -> &Lookup<Attack> {
+        &self.custom_attacks
     }
 
     pub fn selected_fight(&self) -> Option<Arc<dyn PlannerFight>> {
-        self.fights.get(self.selected_fight_index).cloned()
+        self.fights().get(self.selected_fight_index).cloned()
     }
 
-    pub fn fights(&self) -> &Vec<Arc<dyn PlannerFight>> {
-        &self.fights
+    pub fn selected_difficulty(&self) -> Difficulty {
+        self.selected_difficulty
+    }
+
+    /// Difficulties at least one fight has a timeline for.
+    pub fn difficulties(&self) -> Vec<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .filter(|difficulty| self.fights.iter().any(|fight| fight.difficulty() == *difficulty))
+            .collect()
+    }
+
+    /// Fights available in the selected difficulty.
+    pub fn fights(&self) -> Vec<Arc<dyn PlannerFight>> {
+        self.fights
+            .iter()
+            .filter(|fight| fight.difficulty() == self.selected_difficulty)
+            .cloned()
+            .collect()
     }
 
     pub fn characters(&self) -> &Lookup<PlannerCharacter> {
@@ -247,9 +888,97 @@ impl PlannerState {
     }
 
     pub fn export(&self) -> Option<String> {
-        let selected_fight = self.selected_fight()?;
-        let attacks = selected_fight.attacks();
-        Some(self.characters
+        let header = self.export_header()?;
+        let lines = self.export_lines(|_| true, true);
+        Some([header].into_iter().chain(lines).join("\r\n"))
+    }
+
+    /// One note per character with only their own assignments, for MRT personal notes.
+    pub fn export_personal(&self) -> Option<Vec<(String, String)>> {
+        let header = self.export_header()?;
+        let notes = self
+            .named_characters()
+            .filter_map(|(uuid, name)| {
+                let lines = self.export_lines(
+                    |character| character.uuid == uuid || character.is_general(),
+                    false,
+                );
+                if lines.is_empty() {
+                    return None;
+                }
+                Some((name, [header.clone()].into_iter().chain(lines).join("\r\n")))
+            })
+            .collect();
+        Some(notes)
+    }
+
+    /// Single shared note where every character only sees their own `{p:Name}` block, general
+    /// assignments stay visible to everyone.
+    pub fn export_personal_blocks(&self) -> Option<String> {
+        let header = self.export_header()?;
+        let general = self.export_lines(PlannerCharacter::is_general, true);
+        let blocks = self
+            .named_characters()
+            .filter_map(|(uuid, name)| {
+                let lines = self.export_lines(|character| character.uuid == uuid, false);
+                if lines.is_empty() {
+                    return None;
+                }
+                Some(
+                    [format!("{{p:{name}}}")]
+                        .into_iter()
+                        .chain(lines)
+                        .chain(["{/p}".to_string()])
+                        .join("\r\n"),
+                )
+            })
+            .collect::<Vec<_>>();
+        Some([header].into_iter().chain(general).chain(blocks).join("\r\n"))
+    }
+
+    /// WeakAura import string with a timed reminder for every assignment.
+    pub fn export_weak_aura(&self) -> Option<String> {
+        let header = self.export_header()?;
+        let encounter_id = self
+            .selected_fight()?
+            .data()
+            .as_ref()
+            .map(|data| data.encounter_id)
+            .filter(|id| *id != 0);
+        let attacks = self.attacks();
+        let reminders = self
+            .characters
+            .iter()
+            .flat_map(|character| {
+                character
+                    .assignments
+                    .all()
+                    .map(move |(spell, attack)| (character, spell, attack))
+            })
+            .sorted_by_key(|(_, _, attack)| attacks.get(attack).unwrap().timer.static_timer())
+            .map(|(character, spell, attack)| {
+                let attack = attacks.get(attack).unwrap();
+                let spell = character.spells.get(spell).unwrap();
+                WeakAuraReminder {
+                    player: character
+                        .name
+                        .as_ref()
+                        .filter(|_| !character.is_general())
+                        .map(|name| name.get(self.locale).to_owned()),
+                    text: format!("{} - {}", spell.name.get(self.locale), attack.name.get(self.locale)),
+                    timer: attack.timer.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        Some(reminders_weak_aura(&header, encounter_id, &reminders))
+    }
+
+    /// Plan as Discord messages, one line per attack mentioning the assigned players.
+    pub fn export_discord(&self) -> Option<Vec<String>> {
+        let header = format!("**{}**", self.export_header()?);
+        let attacks = self.attacks();
+        let lines = self
+            .characters
             .iter()
             .filter(|character| character.name.is_some())
             .flat_map(|character| character.assignments.all().map(move |(spell, attack)| (character, spell, attack)))
@@ -257,51 +986,461 @@ impl PlannerState {
             .chunk_by(|(_, _, attack)| **attack)
             .into_iter()
             .map(|(attack_uuid, assignments)| {
-                let characters = assignments
-                    .sorted_by_key(|(character, _, _)| character.name.clone())
-                    .chunk_by(|(character, _, _)| (character.uuid, character.name.clone().unwrap()))
-                    .into_iter()
-                    .map(|((_, character_name), assignments)| {
-                        // TODO: How to do general properly?
-                        [character_name.get(Locale::EnglishUnitedStates).to_owned()].into_iter().chain(assignments.into_iter().map(|(character, spell, _)| {
-                            let identifier = character.spells.get(spell).unwrap().identifier.clone();
-                            identifier.in_game_note().to_string()
-                        })).join(" ")
+                let assignments = assignments
+                    .sorted_by_key(|(character, _, _)| (!character.is_general(), character.name.clone()))
+                    .map(|(character, spell, _)| {
+                        let spell_name = character.spells.get(spell).unwrap().name.get(self.locale).to_owned();
+                        match &character.name {
+                            Some(name) if !character.is_general() => format!("@{} {spell_name}", name.get(self.locale)),
+                            _ => spell_name,
+                        }
                     })
-                    .join("  ");
+                    .join(", ");
                 let attack = attacks.get(&attack_uuid).unwrap();
-                let dynamic_timer = attack.timer.dynamic_timer.unwrap_or(TimeStep::zero());
-                let spell_trigger = match &attack.timer.dynamic_trigger_cleu_event {
-                    Some(cleu_event) => {
-                        let event_type = &cleu_event.r#type;
-                        let event_id = cleu_event.event;
-                        let counter = cleu_event.counter;
-                        format!(",{event_type}:{event_id}:{counter}")
-                    },
-                    None => "".to_string(),
-                };
                 let static_timer = attack.timer.static_timer();
-                let attack_name = &attack.name;
-                format!("{{time:{dynamic_timer}{spell_trigger}}}{static_timer} - {attack_name} - {characters}")
-            }).join("\r\n")
-        )
+                format!("`{static_timer}` **{}** - {assignments}", attack.name.get(self.locale))
+            })
+            .collect::<Vec<_>>();
+        Some(discord_messages([header].into_iter().chain(lines)))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use uuid::uuid;
+    /// Discord messages with a block per player listing their own assignments.
+    pub fn export_discord_personal(&self) -> Option<Vec<String>> {
+        let header = format!("**{}**", self.export_header()?);
+        let attacks = self.attacks();
+        let blocks = self.named_characters().filter_map(|(character_uuid, name)| {
+            let character = self.characters.get(&character_uuid).unwrap();
+            if character.assignments.is_empty() {
+                return None;
+            }
+            let lines = character
+                .assignments
+                .all()
+                .sorted_by_key(|(_, attack)| attacks.get(attack).unwrap().timer.static_timer())
+                .map(|(spell, attack)| {
+                    let attack = attacks.get(attack).unwrap();
+                    let spell = character.spells.get(spell).unwrap();
+                    let static_timer = attack.timer.static_timer();
+                    format!("`{static_timer}` {} - {}", attack.name.get(self.locale), spell.name.get(self.locale))
+                });
+            Some([format!("@{name}")].into_iter().chain(lines).join("\n"))
+        });
+        Some(discord_messages([header].into_iter().chain(blocks)))
+    }
 
-    use auto_battle_net::game_data::journal::{journal_encounter, journal_instance};
-    use auto_battle_net::game_data::journal::journal_encounter::JournalEncounterResponse;
-    use auto_battle_net::game_data::journal::journal_instance::JournalInstanceResponse;
-    use auto_battle_net::Link;
+    /// Assignment grid with a column for every spell of every named character.
+    pub fn export_sheet(&self) -> Option<Sheet> {
+        self.selected_fight()?;
+        let columns = self
+            .characters
+            .iter()
+            .filter_map(|character| Some((character, character.name.as_ref()?)))
+            .flat_map(|(character, name)| character.spells.iter().map(move |spell| (character, name, spell)))
+            .collect::<Vec<_>>();
+
+        let characters_row = [TIME_HEADER, ATTACK_HEADER, TYPE_HEADER]
+            .map(str::to_string)
+            .into_iter()
+            .chain(columns.iter().map(|(_, name, _)| name.get(self.locale).to_owned()))
+            .collect();
+        let spells_row = vec![String::new(); ATTACK_COLUMNS]
+            .into_iter()
+            .chain(columns.iter().map(|(_, _, spell)| spell.name.get(self.locale).to_owned()))
+            .collect();
+        let attacks = self.attacks();
+        let attack_rows = attacks.iter().map(|attack| {
+            [
+                attack.timer.static_timer().to_string(),
+                attack.name.get(self.locale).to_owned(),
+                attack.r#type.color_name().to_string(),
+            ]
+            .into_iter()
+            .chain(columns.iter().map(|(character, _, spell)| {
+                match character.assignment_state(spell.uuid, attack.uuid) {
+                    AssignmentState::Locked => LOCKED_CELL.to_string(),
+                    AssignmentState::Suggested => SUGGESTED_CELL.to_string(),
+                    AssignmentState::Unassigned => String::new(),
+                }
+            }))
+            .collect()
+        });
+
+        let rows = [characters_row, spells_row].into_iter().chain(attack_rows).collect();
+        Some(Sheet { rows })
+    }
+
+    fn export_header(&self) -> Option<String> {
+        let selected_fight = self.selected_fight()?;
+        let header = selected_fight
+            .data()
+            .iter()
+            .map(|data| data.encounter_name.get(self.locale).to_owned())
+            .chain([selected_fight.difficulty().to_string()])
+            .filter(|part| !part.is_empty())
+            .join(" - ");
+        Some(header)
+    }
+
+    fn named_characters(&self) -> impl Iterator<Item = (CharacterUuid, String)> + '_ {
+        self.characters
+            .iter()
+            .filter(|character| !character.is_general())
+            .filter_map(|character| {
+                let name = character.name.as_ref()?;
+                Some((character.uuid, name.get(self.locale).to_owned()))
+            })
+            .sorted_by(|(_, a), (_, b)| a.cmp(b))
+    }
+
+    fn export_lines(
+        &self,
+        filter: impl Fn(&PlannerCharacter) -> bool,
+        with_names: bool,
+    ) -> Vec<String> {
+        let attacks = self.attacks();
+        self.characters
+            .iter()
+            .filter(|character| character.name.is_some() && filter(character))
+            .flat_map(|character| character.assignments.all().map(move |(spell, attack)| (character, spell, attack)))
+            .sorted_by_key(|(_, _, attack)| attacks.get(attack).unwrap().timer.static_timer())
+            .chunk_by(|(_, _, attack)| **attack)
+            .into_iter()
+            .map(|(attack_uuid, assignments)| {
+                // general assignments go to the whole raid, ahead of the players
+                let characters = assignments
+                    .sorted_by_key(|(character, _, _)| (!character.is_general(), character.name.clone()))
+                    .chunk_by(|(character, _, _)| (character.uuid, character.name.clone().unwrap()))
+                    .into_iter()
+                    .map(|((character_uuid, character_name), assignments)| {
+                        let name = if character_uuid == CharacterUuid::general() {
+                            Some(EVERYONE_TAG.to_string())
+                        } else {
+                            with_names.then(|| character_name.get(self.locale).to_owned())
+                        };
+                        name.into_iter().chain(assignments.into_iter().map(|(character, spell, _)| {
+                            let identifier = character.spells.get(spell).unwrap().identifier.clone();
+                            identifier.in_game_note().to_string()
+                        })).join(" ")
+                    })
+                    .join("  ");
+                let attack = attacks.get(&attack_uuid).unwrap();
+                let dynamic_timer = attack.timer.dynamic_timer.unwrap_or(TimeStep::zero());
+                let spell_trigger = match &attack.timer.dynamic_trigger_cleu_event {
+                    Some(cleu_event) => {
+                        let event_type = &cleu_event.r#type;
+                        let event_id = cleu_event.event;
+                        let counter = cleu_event.counter;
+                        format!(",{event_type}:{event_id}:{counter}")
+                    },
+                    None => "".to_string(),
+                };
+                let static_timer = attack.timer.static_timer();
+                let name = attack.name.get(self.locale);
+                let attack_name = match &attack.identifier {
+                    Some(identifier) => format!("{} {name}", identifier.in_game_note()),
+                    None => name.to_owned(),
+                };
+                format!("{{time:{dynamic_timer}{spell_trigger}}}{static_timer} - {attack_name} - {characters}")
+            })
+            .collect()
+    }
+
+    /// Lines up the assignments of named characters with their casts in a pull of a combat log,
+    /// spells without a spell id cannot be found in logs and are left out.
+    pub fn verify_plan(&self, encounter: &CombatLogEncounter) -> Vec<CastVerification> {
+        let attacks = &self.attacks();
+        let planned = self
+            .characters
+            .iter()
+            .filter(|character| !character.is_general())
+            .filter_map(|character| {
+                let name = character.name.as_ref()?.get(Locale::EnglishUnitedStates).to_owned();
+                Some((character, name))
+            })
+            .flat_map(|(character, name)| {
+                character.assignments.all().filter_map(move |(spell, attack)| {
+                    let spell = character.spells.get(spell).unwrap();
+                    let Identifier::Spell(spell_id) = spell.identifier else {
+                        return None;
+                    };
+                    Some(PlannedCast {
+                        character: character.uuid,
+                        character_name: name.clone(),
+                        spell: spell.uuid,
+                        spell_id,
+                        cooldown: spell.cooldown,
+                        attack: *attack,
+                        timer: attacks.get(attack).unwrap().timer.clone(),
+                    })
+                })
+            })
+            .collect();
+        verify_casts(planned, encounter)
+    }
+
+    /// Locks the assignments of an MRT note, reporting the lines, names and icons that could not
+    /// be matched to attacks, characters and spells.
+    pub fn import_note(&mut self, note: &str) -> NoteImportReport {
+        self.edit(PlannerEdit::ImportNote, |state| state.import_note_unrecorded(note))
+    }
+
+    fn import_note_unrecorded(&mut self, note: &str) -> NoteImportReport {
+        let attacks = self.attacks();
+        // hand-written notes are often a few seconds off
+        let tolerance = TimeStep::mm_ss(0, 3);
+        let mut report = NoteImportReport::default();
+
+        for line in parse_note(note) {
+            let exact = attacks
+                .iter()
+                .filter(|attack| line.time.matches(&attack.timer))
+                .collect::<Vec<_>>();
+            // several attacks can share a timer, prefer the one named in the line
+            let attack = exact
+                .iter()
+                .find(|attack| line.text.contains(attack.name.get(self.locale)))
+                .or(exact.first())
+                .copied()
+                .or_else(|| {
+                    attacks
+                        .iter()
+                        .filter(|attack| {
+                            line.time.trigger.is_none()
+                                && attack.timer.dynamic_trigger_cleu_event.is_none()
+                                && attack.timer.static_timer().abs_diff(line.time.timer)
+                                    <= tolerance
+                        })
+                        .min_by_key(|attack| attack.timer.static_timer().abs_diff(line.time.timer))
+                });
+            let Some(attack) = attack else {
+                report.problems.push(NoteImportProblem::UnknownAttack {
+                    line: line.line,
+                    time: line.time.to_string(),
+                });
+                continue;
+            };
+            let attack = attack.uuid;
+
+            // icons in front of any name are the boss ability, not an assignment
+            for mut entry in line
+                .entries
+                .into_iter()
+                .filter(|entry| entry.everyone || !entry.name.is_empty())
+            {
+                // `{everyone}` lines hold the assignments of the general character
+                let character = if entry.everyone {
+                    entry.name = EVERYONE_TAG.to_string();
+                    self.characters
+                        .iter()
+                        .find(|character| character.is_general())
+                        .map(|character| character.uuid)
+                } else {
+                    self.characters
+                        .iter()
+                        .filter(|character| !character.is_general())
+                        .find(|character| {
+                            character.name.as_ref().is_some_and(|name| {
+                                note_name_matches(&entry.name, name.get(Locale::EnglishUnitedStates))
+                            })
+                        })
+                        .map(|character| character.uuid)
+                };
+                let Some(character) = character else {
+                    report.problems.push(NoteImportProblem::UnknownCharacter {
+                        line: line.line,
+                        name: entry.name,
+                    });
+                    continue;
+                };
+
+                for icon in entry.icons {
+                    // prefer enabled variants of a spell and skip the ones that cannot be assigned
+                    let spells = self
+                        .characters
+                        .get(&character)
+                        .unwrap()
+                        .spells
+                        .iter()
+                        .filter(|spell| icon.matches(&spell.identifier))
+                        .sorted_by_key(|spell| !spell.enabled)
+                        .map(|spell| spell.uuid)
+                        .collect::<Vec<_>>();
+                    if spells.is_empty() {
+                        report.problems.push(NoteImportProblem::UnknownSpell {
+                            line: line.line,
+                            name: entry.name.clone(),
+                            icon,
+                        });
+                        continue;
+                    }
+
+                    let already_locked = spells.iter().any(|spell| {
+                        self.characters
+                            .get(&character)
+                            .unwrap()
+                            .assignment_state(*spell, attack)
+                            == AssignmentState::Locked
+                    });
+                    if already_locked {
+                        continue;
+                    }
+                    let assignable = spells.into_iter().find(|spell| {
+                        self.is_spell_assignable(character, *spell, attack)
+                            == Assignability::Assignable
+                    });
+                    let Some(spell) = assignable else {
+                        report.problems.push(NoteImportProblem::NotAssignable {
+                            line: line.line,
+                            name: entry.name.clone(),
+                            icon,
+                        });
+                        continue;
+                    };
+                    let character = self.characters.get_mut(&character).unwrap();
+                    character.assignments.assign_locked(spell, attack);
+                    report.imported += 1;
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Locks the locked cells of an exported assignment grid and takes over its suggestions,
+    /// reporting the rows and columns that could not be matched to attacks, characters and spells.
+    pub fn import_sheet(&mut self, sheet: &Sheet) -> SheetImportReport {
+        self.edit(PlannerEdit::ImportSheet, |state| state.import_sheet_unrecorded(sheet))
+    }
+
+    fn import_sheet_unrecorded(&mut self, sheet: &Sheet) -> SheetImportReport {
+        let attacks = self.attacks();
+        let mut report = SheetImportReport::default();
+
+        let mut columns = vec![];
+        for column in ATTACK_COLUMNS..sheet.columns() {
+            let name = sheet.cell(0, column);
+            let spell_name = sheet.cell(1, column);
+            if name.is_empty() && spell_name.is_empty() {
+                continue;
+            }
+            let character = self.characters.iter().find(|character| {
+                character
+                    .name
+                    .as_ref()
+                    .is_some_and(|character_name| note_name_matches(name, character_name.get(self.locale)))
+            });
+            let Some(character) = character else {
+                report.problems.push(SheetImportProblem::UnknownCharacter {
+                    column,
+                    name: name.to_string(),
+                });
+                continue;
+            };
+            // a spell can show up in several columns, e.g. when it was added twice
+            let spell = character.spells.iter().find(|spell| {
+                spell.name.get(self.locale) == spell_name
+                    && !columns
+                        .iter()
+                        .any(|(_, taken_character, taken_spell)| {
+                            *taken_character == character.uuid && *taken_spell == spell.uuid
+                        })
+            });
+            let Some(spell) = spell else {
+                report.problems.push(SheetImportProblem::UnknownSpell {
+                    column,
+                    name: name.to_string(),
+                    spell: spell_name.to_string(),
+                });
+                continue;
+            };
+            columns.push((column, character.uuid, spell.uuid));
+        }
+
+        let mut suggestions = columns
+            .iter()
+            .map(|(_, character, _)| (*character, vec![]))
+            .collect::<HashMap<_, _>>();
+        for row in 2..sheet.rows.len() {
+            let time = sheet.cell(row, 0);
+            let name = sheet.cell(row, 1);
+            if time.is_empty() && name.is_empty() {
+                continue;
+            }
+            let named = attacks
+                .iter()
+                .filter(|attack| attack.name.get(self.locale) == name)
+                .collect::<Vec<_>>();
+            // spreadsheets like to drop the leading zeros of the minutes
+            let attack = named
+                .iter()
+                .find(|attack| {
+                    attack.timer.static_timer().to_string().trim_start_matches('0')
+                        == time.trim_start_matches('0')
+                })
+                .or(named.first());
+            let Some(attack) = attack else {
+                report.problems.push(SheetImportProblem::UnknownAttack {
+                    row,
+                    name: name.to_string(),
+                    time: time.to_string(),
+                });
+                continue;
+            };
+            let attack = attack.uuid;
+
+            for &(column, character, spell) in &columns {
+                match SheetCellState::parse(sheet.cell(row, column)) {
+                    Some(SheetCellState::Locked) => {
+                        let already_locked = self
+                            .characters
+                            .get(&character)
+                            .unwrap()
+                            .assignment_state(spell, attack)
+                            == AssignmentState::Locked;
+                        if already_locked {
+                            continue;
+                        }
+                        if self.is_spell_assignable(character, spell, attack) != Assignability::Assignable {
+                            report.problems.push(SheetImportProblem::NotAssignable { row, column });
+                            continue;
+                        }
+                        let character = self.characters.get_mut(&character).unwrap();
+                        character.assignments.assign_locked(spell, attack);
+                        report.imported += 1;
+                    }
+                    Some(SheetCellState::Suggested) => {
+                        suggestions.get_mut(&character).unwrap().push((spell, attack));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        for (character, suggestions) in suggestions {
+            let character = self.characters.get_mut(&character).unwrap();
+            character.assignments.replace_suggestions(suggestions);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::uuid;
+
+    use auto_battle_net::game_data::journal::{journal_encounter, journal_instance};
+    use auto_battle_net::game_data::journal::journal_encounter::JournalEncounterResponse;
+    use auto_battle_net::game_data::journal::journal_instance::JournalInstanceResponse;
+    use auto_battle_net::Link;
     use dragonflight::aberrus::kazzara::Kazzara;
     use fight_domain::AttackUuid;
     use i18n::LocalizedString;
+    use ordered_float::NotNan;
 
     use crate::fights::dragonflight;
-    use crate::PlannerRealm;
+    use crate::combat_log::{parse_combat_log, CastTiming};
+    use crate::fights::note_fight::NoteFight;
+    use crate::{NoteIcon, PlannerRealm};
 
     use super::*;
 
@@ -335,7 +1474,7 @@ mod tests {
     fn replace_character() {
         let mut state = PlannerState::new(vec![]);
         let uuid_added = state.add_character(PlannerCharacterTemplate::Unknown);
-        let uuid_replaced = state.replace_character(
+        let (uuid_replaced, _) = state.replace_character(
             uuid_added,
             PlannerCharacterTemplate::Custom {
                 name: "Test".to_string(),
@@ -727,6 +1866,835 @@ mod tests {
         }
     }
 
+    #[test]
+    fn selected_difficulty_filters_fights() {
+        let mut state = PlannerState::new(vec![
+            Arc::new(NoteFight::new("Custom Boss", Difficulty::Heroic, "{time:00:10}Adds")),
+            Arc::new(Kazzara::mythic(
+                Some(&mock_journal_instance()),
+                Some(&mock_journal_encounter()),
+            )),
+        ]);
+        assert_eq!(state.selected_difficulty(), Difficulty::Mythic);
+        assert_eq!(state.fights().len(), 1);
+        assert_eq!(state.selected_fight().unwrap().difficulty(), Difficulty::Mythic);
+
+        state.set_selected_difficulty(Difficulty::Heroic);
+
+        assert_eq!(state.fights().len(), 1);
+        assert_eq!(state.selected_fight().unwrap().difficulty(), Difficulty::Heroic);
+        assert_eq!(
+            state.selected_fight().unwrap().data().as_ref().unwrap().difficulty,
+            Difficulty::Heroic
+        );
+    }
+
+    #[test]
+    fn only_difficulties_with_fights_are_offered() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        assert_eq!(state.difficulties(), vec![Difficulty::Mythic]);
+
+        state.add_fight(Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Heroic,
+            "{time:00:10}Adds",
+        )));
+
+        assert_eq!(state.difficulties(), vec![Difficulty::Heroic, Difficulty::Mythic]);
+    }
+
+    #[test]
+    fn add_fight_selects_it() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(
+            Some(&mock_journal_instance()),
+            Some(&mock_journal_encounter()),
+        ))]);
+
+        state.add_fight(Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Heroic,
+            "{time:00:10}Adds",
+        )));
+
+        assert_eq!(state.selected_difficulty(), Difficulty::Heroic);
+        assert_eq!(state.fights().len(), 1);
+        let fight = state.selected_fight().unwrap();
+        assert_eq!(
+            fight.data().as_ref().unwrap().encounter_name,
+            LocalizedString::constant("Custom Boss")
+        );
+        assert_eq!(fight.attacks().len(), 1);
+    }
+
+    #[test]
+    fn switching_fights_removes_assignments_to_missing_attacks() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+
+        state.add_fight(Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Mythic,
+            "{time:00:10}Adds",
+        )));
+
+        assert!(state.characters().get(&character).unwrap().assignments.is_empty());
+        assert!(state.export().is_some());
+    }
+
+    #[test]
+    fn replace_fights_keeps_added_fights() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        state.add_fight(Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Mythic,
+            "{time:00:10}Adds",
+        )));
+
+        state.replace_fights(vec![Arc::new(Kazzara::mythic(
+            Some(&mock_journal_instance()),
+            Some(&mock_journal_encounter()),
+        ))]);
+
+        let fights = state.fights();
+        assert_eq!(fights.len(), 2);
+        assert!(fights[0].data().is_some());
+        assert_eq!(
+            state.selected_fight().unwrap().data().as_ref().unwrap().encounter_name,
+            LocalizedString::constant("Custom Boss")
+        );
+    }
+
+    #[test]
+    fn export_includes_difficulty() {
+        let mut state = PlannerState::new(vec![Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Heroic,
+            "{time:00:10}Adds",
+        ))]);
+        state.set_selected_difficulty(Difficulty::Heroic);
+
+        let export = state.export().unwrap();
+
+        assert_eq!(export.lines().next(), Some("Custom Boss - Heroic"));
+    }
+
+    fn bloodlust() -> CustomAttackTemplate {
+        CustomAttackTemplate {
+            name: "Bloodlust".to_string(),
+            identifier: None,
+            power: NotNan::new(0.0).unwrap(),
+            r#type: fight_domain::AttackType::Generic,
+            timer: fight_domain::AttackTimer {
+                dynamic_timer: Some(TimeStep::mm_ss(0, 10)),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn custom_attacks_are_merged_into_fight_attacks() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        let fight_attack_count = state.attacks().len();
+
+        let uuid = state.add_custom_attack(bloodlust());
+
+        let attacks = state.attacks();
+        assert_eq!(attacks.len(), fight_attack_count + 1);
+        assert_eq!(state.custom_attacks().len(), 1);
+        // on pull at 0:03, bloodlust at 0:10, first knock at 0:17
+        let order = attacks.iter().map(|a| a.uuid).collect::<Vec<_>>();
+        assert_eq!(order[0], ON_PULL);
+        assert_eq!(order[1], uuid);
+        assert_eq!(order[2], KNOCK_AOE);
+    }
+
+    #[test]
+    fn custom_attacks_stay_with_their_fight() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        let uuid = state.add_custom_attack(bloodlust());
+
+        state.add_fight(Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Mythic,
+            "{time:00:10}Adds",
+        )));
+
+        assert!(state.custom_attacks().is_empty());
+        assert!(!state.attacks().contains_key(&uuid));
+        assert!(state.saved_plan().custom_attacks.is_empty());
+
+        state.undo();
+        assert!(state.custom_attacks().contains_key(&uuid));
+    }
+
+    #[test]
+    fn selecting_a_fight_loads_its_saved_plan() {
+        let mut other = PlannerState::new(vec![Arc::new(NoteFight::new(
+            "Custom Boss",
+            Difficulty::Mythic,
+            "{time:00:10}Adds",
+        ))]);
+        let alice = other.add_character(PlannerCharacterTemplate::Custom { name: "Alice".to_string() });
+        let saved = other.saved_plan();
+        let (mut state, character) = base_assignability_setup();
+        state.set_fight_plan_loader(Arc::new(move |fight| (*fight == saved.fight).then(|| saved.clone())));
+
+        state.add_fight(Arc::new(NoteFight::new("Custom Boss", Difficulty::Mythic, "{time:00:10}Adds")));
+
+        assert!(state.characters().contains_key(&alice));
+        assert!(!state.characters().contains_key(&character));
+        assert_eq!(state.undo(), Some(PlannerEdit::AddFight));
+        assert!(state.characters().contains_key(&character));
+        assert!(!state.characters().contains_key(&alice));
+    }
+
+    #[test]
+    fn update_custom_attack() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        let uuid = state.add_custom_attack(bloodlust());
+
+        let mut template = bloodlust();
+        template.name = "Pre-pot".to_string();
+        template.timer.dynamic_timer = Some(TimeStep::zero());
+        state.update_custom_attack(uuid, template);
+
+        let attacks = state.attacks();
+        let attack = attacks.get(&uuid).unwrap();
+        assert_eq!(attack.name, LocalizedString::constant("Pre-pot"));
+        assert_eq!(attacks.iter().next().unwrap().uuid, uuid);
+    }
+
+    #[test]
+    fn apply_attack_damage_to_custom_attacks() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        let uuid = state.add_custom_attack(CustomAttackTemplate {
+            identifier: Some(Identifier::Spell(401319)),
+            ..bloodlust()
+        });
+        let bloodlust = state.add_custom_attack(bloodlust());
+        let damage = AttackDamage {
+            spell_id: 401319,
+            name: "Hellsteel Carnage".to_string(),
+            occurrences: 2,
+            damage: 800,
+            targets: 20.0,
+            power: NotNan::new(1.5).unwrap(),
+            r#type: fight_domain::AttackType::RaidDamage,
+        };
+
+        state.apply_attack_damage(&[damage]);
+
+        let attack = state.custom_attacks().get(&uuid).unwrap();
+        assert_eq!(attack.power, NotNan::new(1.5).unwrap());
+        assert_eq!(attack.r#type, fight_domain::AttackType::RaidDamage);
+        let bloodlust = state.custom_attacks().get(&bloodlust).unwrap();
+        assert_eq!(bloodlust.r#type, fight_domain::AttackType::Generic);
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_fight_attack_panics() {
+        let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(None, None))]);
+        state.update_custom_attack(ON_PULL, bloodlust());
+    }
+
+    #[test]
+    fn remove_custom_attack_removes_assignments() {
+        let (mut state, character) = base_assignability_setup();
+        let uuid = state.add_custom_attack(bloodlust());
+        state.toggle_assignment(character, TRANQ_3M, uuid);
+        assert_eq!(
+            state.characters().get(&character).unwrap().assignments.locked().count(),
+            1
+        );
+
+        state.remove_custom_attack(uuid);
+
+        assert!(!state.attacks().contains_key(&uuid));
+        assert!(state.characters().get(&character).unwrap().assignments.is_empty());
+    }
+
+    #[test]
+    fn export_includes_custom_attacks() {
+        let (mut state, character) = base_assignability_setup();
+        let uuid = state.add_custom_attack(bloodlust());
+        state.toggle_assignment(character, TRANQ_3M, uuid);
+
+        let export = state.export().unwrap();
+
+        assert!(export.contains("{time:00:10}00:10 - Bloodlust - Test {spell:740}"));
+    }
+
+    #[test]
+    fn spell_names_apply_to_new_characters() {
+        let (mut state, character) = base_assignability_setup();
+        assert!(state.spell_ids().contains(&740));
+        let tranquility = LocalizedString::new(
+            "宁静", "寧靜", "Tranquility", "Tranquility", "Tranquillité", "Gelassenheit",
+            "Tranquillità", "평온", "Tranquilidade", "Спокойствие", "Tranquilidad", "Tranquilidad",
+        );
+
+        state.set_spell_names([(740, tranquility)].into());
+        let other = state.add_character(PlannerCharacterTemplate::Custom {
+            name: "Other".to_string(),
+        });
+        state.change_character_class(other, LocalizedString::constant("Druid"));
+        state.change_character_spec(other, LocalizedString::constant("Restoration"));
+
+        for uuid in [character, other] {
+            let spell = state.characters().get(&uuid).unwrap().spells.get(&TRANQ_3M).unwrap().clone();
+            assert_eq!(spell.name.get(Locale::German), "Gelassenheit 3m");
+        }
+    }
+
+    #[test]
+    fn export_general_for_everyone() {
+        let (mut state, character) = base_assignability_setup();
+        let healthstone = SpellUuid::new(uuid!("ac3f5e1b-4868-409c-9558-2c457ba9360b"));
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(CharacterUuid::general(), healthstone, ON_PULL);
+
+        let export = state.export().unwrap();
+
+        assert!(export.contains("On Pull - {everyone} {icon:538745}  Test {spell:740}"));
+        assert!(!export.contains("General"));
+
+        let blocks = state.export_personal_blocks().unwrap();
+        let lines = blocks.lines().collect::<Vec<_>>();
+        assert!(lines[1].ends_with("On Pull - {everyone} {icon:538745}"));
+        assert_eq!(lines[2], "{p:Test}");
+        assert!(lines[3].ends_with("On Pull - {spell:740}"));
+    }
+
+    #[test]
+    fn export_weak_aura() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+
+        let export = state.export_weak_aura().unwrap();
+
+        assert!(export.starts_with("!WA:2!"));
+    }
+
+    #[test]
+    fn export_personal_notes() {
+        let (mut state, character) = base_assignability_setup();
+        let other = state.add_character(PlannerCharacterTemplate::Known {
+            name: "Other".to_string(),
+            realm: PlannerRealm {
+                name: LocalizedString::constant("Test"),
+                slug: "test".to_string(),
+            },
+            class: LocalizedString::constant("Druid"),
+            spec: LocalizedString::constant("Restoration"),
+        });
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(other, CONVOKE, AOE_80_PERCENT);
+
+        let notes = state.export_personal().unwrap();
+
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].0, "Other");
+        assert!(notes[0].1.contains("80% HP AoE - {spell:323764}"));
+        assert!(!notes[0].1.contains("{spell:740}"));
+        assert_eq!(notes[1].0, "Test");
+        assert!(notes[1].1.contains("Pull - {spell:740}"));
+        assert!(!notes[1].1.contains("{spell:323764}"));
+
+        let blocks = state.export_personal_blocks().unwrap();
+        let lines = blocks.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "{p:Other}");
+        assert!(lines[2].ends_with("{spell:323764}"));
+        assert_eq!(lines[3], "{/p}");
+        assert_eq!(lines[4], "{p:Test}");
+        assert!(lines[5].ends_with("{spell:740}"));
+        assert_eq!(lines[6], "{/p}");
+    }
+
+    #[test]
+    fn export_discord_mentions_players() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, AOE_80_PERCENT);
+
+        let messages = state.export_discord().unwrap();
+
+        assert_eq!(messages.len(), 1);
+        let lines = messages[0].lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("**"));
+        assert!(lines[1].contains("**On Pull** - @Test Tranquility 3m"));
+        assert!(!lines[1].contains("{spell:740}"));
+        assert!(lines[2].contains("**80% HP AoE** - @Test Convoke the Spirits"));
+
+        let messages = state.export_discord_personal().unwrap();
+        let lines = messages[0].lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "@Test");
+        assert!(lines[2].ends_with("On Pull - Tranquility 3m"));
+        assert!(lines[3].ends_with("80% HP AoE - Convoke the Spirits"));
+    }
+
+    #[test]
+    fn verify_plan_against_log() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, AOE_80_PERCENT);
+        let log = r#"4/19 20:31:45.123  ENCOUNTER_START,2688,"Kazzara, the Hellforged",16,20,2569
+4/19 20:31:49.000  SPELL_CAST_SUCCESS,Player-1-1,"Test-Test-EU",0x514,0x0,0000000000000000,nil,0x80000000,0x80000000,740,"Tranquility",0x8
+4/19 20:35:45.123  ENCOUNTER_END,2688,"Kazzara, the Hellforged",16,20,1,240000"#;
+        let encounter = &parse_combat_log(log)[0];
+
+        let verification = state.verify_plan(encounter);
+
+        assert_eq!(verification.len(), 2);
+        assert_eq!(verification[0].planned.spell, TRANQ_3M);
+        assert_eq!(verification[0].timing, CastTiming::OnTime);
+        assert_eq!(verification[1].planned.spell, CONVOKE);
+        // the log has no cast of the attack's trigger
+        assert_eq!(verification[1].timing, CastTiming::NotDue);
+    }
+
+    #[test]
+    fn saved_plan_round_trip() {
+        let (mut state, character) = base_assignability_setup();
+        let custom_attack = state.add_custom_attack(bloodlust());
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, custom_attack);
+        state.toggle_spell_enabled(character, TRANQ_3M);
+        let plan = state.saved_plan();
+
+        let mut loaded = PlannerState::new(state.fights.clone());
+        loaded.load_saved_plan(&SavedPlan::from_json(&plan.to_json()).unwrap());
+
+        assert_eq!(loaded.custom_attacks(), state.custom_attacks());
+        assert_eq!(loaded.characters(), state.characters());
+        assert_eq!(loaded.saved_plan(), plan);
+    }
+
+    #[test]
+    fn load_saved_plan_drops_assignments_to_missing_attacks() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        let mut plan = state.saved_plan();
+        plan.fight.encounter_id = 1;
+
+        let mut loaded = PlannerState::new(vec![]);
+        loaded.load_saved_plan(&plan);
+
+        let loaded_character = loaded.characters().get(&character).unwrap();
+        assert!(loaded_character.assignments.is_empty());
+        assert_eq!(loaded.characters().len(), 2);
+    }
+
+    #[test]
+    fn replace_character_carries_over_assignments() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+
+        let (replaced, dropped) = state.replace_character(
+            character,
+            PlannerCharacterTemplate::Custom {
+                name: "Other".to_string(),
+            },
+        );
+
+        let dropped = dropped.iter().map(|d| (d.spell, d.attack)).collect::<Vec<_>>();
+        assert_eq!(dropped, [(TRANQ_3M, ON_PULL)]);
+        assert!(state.characters().get(&replaced).unwrap().assignments.is_empty());
+
+        let other = state.add_character(PlannerCharacterTemplate::Unknown);
+        state.change_character_class(other, LocalizedString::constant("Druid"));
+        state.change_character_spec(other, LocalizedString::constant("Restoration"));
+        state.toggle_assignment(other, TRANQ_3M, ON_PULL);
+        let (replaced, dropped) = state.replace_character(
+            other,
+            PlannerCharacterTemplate::Known {
+                name: "Known".to_string(),
+                realm: PlannerRealm {
+                    name: LocalizedString::constant("Test"),
+                    slug: "test".to_string(),
+                },
+                class: LocalizedString::constant("Druid"),
+                spec: LocalizedString::constant("Restoration"),
+            },
+        );
+
+        assert!(dropped.is_empty());
+        let locked = state
+            .characters()
+            .get(&replaced)
+            .unwrap()
+            .assignments
+            .locked()
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(locked, [(TRANQ_3M, ON_PULL)]);
+    }
+
+    #[test]
+    fn carried_over_assignments_are_checked_for_assignability() {
+        let (mut state, character) = base_assignability_setup();
+        // overrides are not carried over, without them the spells conflict
+        for (spell, spell_override) in [
+            (TRANQ_3M, SpellOverride { cooldown: Some(TimeStep::mm_ss(0, 10)), ..Default::default() }),
+            (TREE_OF_LIFE, SpellOverride { exclusive_with: Some(Default::default()), ..Default::default() }),
+        ] {
+            state.override_spell(character, spell, spell_override);
+        }
+        for (spell, attack) in [
+            (TRANQ_3M, ON_PULL),
+            (TRANQ_3M, KNOCK_AOE),
+            (CONVOKE, ON_PULL),
+            (TREE_OF_LIFE, KNOCK_AOE),
+        ] {
+            state.toggle_assignment(character, spell, attack);
+        }
+        assert_eq!(state.characters().get(&character).unwrap().assignments.locked().count(), 4);
+
+        let (replaced, dropped) = state.replace_character(
+            character,
+            PlannerCharacterTemplate::Custom {
+                name: "Other".to_string(),
+            },
+        );
+        assert_eq!(dropped.len(), 4);
+        assert!(state.characters().get(&replaced).unwrap().assignments.is_empty());
+
+        state.undo();
+        let (replaced, dropped) = state.replace_character(
+            character,
+            PlannerCharacterTemplate::Known {
+                name: "Known".to_string(),
+                realm: PlannerRealm {
+                    name: LocalizedString::constant("Test"),
+                    slug: "test".to_string(),
+                },
+                class: LocalizedString::constant("Druid"),
+                spec: LocalizedString::constant("Restoration"),
+            },
+        );
+
+        let mut dropped = dropped.iter().map(|d| (d.spell, d.attack)).collect::<Vec<_>>();
+        dropped.sort();
+        let mut expected = vec![(TRANQ_3M, KNOCK_AOE), (TREE_OF_LIFE, KNOCK_AOE)];
+        expected.sort();
+        assert_eq!(dropped, expected);
+        let mut locked = state
+            .characters()
+            .get(&replaced)
+            .unwrap()
+            .assignments
+            .locked()
+            .copied()
+            .collect::<Vec<_>>();
+        locked.sort();
+        let mut expected = vec![(TRANQ_3M, ON_PULL), (CONVOKE, ON_PULL)];
+        expected.sort();
+        assert_eq!(locked, expected);
+    }
+
+    #[test]
+    fn undo_and_redo_edits() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        let assigned = state.characters().clone();
+        state.change_character_spec(character, LocalizedString::constant("Balance"));
+
+        assert_eq!(state.undo(), Some(PlannerEdit::ChangeSpec));
+        assert_eq!(state.characters(), &assigned);
+        assert_eq!(state.next_redo(), Some(PlannerEdit::ChangeSpec));
+        assert_eq!(state.redo(), Some(PlannerEdit::ChangeSpec));
+        assert!(state
+            .characters()
+            .get(&character)
+            .unwrap()
+            .assignments
+            .is_empty());
+
+        state.undo();
+        state.undo();
+        state.undo();
+        assert_eq!(state.undo(), None);
+        assert_eq!(state.characters().len(), 1);
+
+        state.redo();
+        state.toggle_spell_enabled(character, CONVOKE);
+        assert_eq!(state.next_redo(), None);
+        // suggestions come from the optimizer, not the user
+        state.replace_assignment_suggestions(Lookup::default());
+        assert_eq!(state.next_undo(), Some(PlannerEdit::ToggleSpell));
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.undo();
+        assert_eq!(state.next_redo(), Some(PlannerEdit::ToggleAssignment));
+
+        state.toggle_spell_enabled(character, CONVOKE);
+
+        assert_eq!(state.next_redo(), None);
+        assert_eq!(state.redo(), None);
+    }
+
+    #[test]
+    fn history_keeps_the_latest_edits() {
+        let (mut state, character) = base_assignability_setup();
+        let before = state.characters().clone();
+        for _ in 0..101 {
+            state.toggle_spell_enabled(character, CONVOKE);
+        }
+
+        for _ in 0..100 {
+            assert_eq!(state.undo(), Some(PlannerEdit::ToggleSpell));
+        }
+
+        assert_eq!(state.undo(), None);
+        // the oldest edit is forgotten, so the character is left after it
+        assert_ne!(state.characters(), &before);
+    }
+
+    #[test]
+    fn loading_a_plan_clears_history() {
+        let (mut state, character) = base_assignability_setup();
+        let plan = state.saved_plan();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.undo();
+
+        state.load_saved_plan(&plan);
+        assert_eq!(state.next_undo(), None);
+        assert_eq!(state.next_redo(), None);
+
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        let code = state.share_code();
+        state.toggle_spell_enabled(character, CONVOKE);
+        state.undo();
+        state.load_share_code(&code).unwrap();
+        assert_eq!(state.undo(), None);
+        assert_eq!(state.redo(), None);
+    }
+
+    #[test]
+    fn undo_keeps_the_latest_suggestions() {
+        let (mut state, character) = base_assignability_setup();
+        let suggest = |state: &mut PlannerState, attack| {
+            state.replace_assignment_suggestions(
+                [Assignment {
+                    character,
+                    spell: TRANQ_3M,
+                    attack,
+                    state: AssignmentState::Suggested,
+                }]
+                .into_iter()
+                .collect(),
+            )
+        };
+        let suggested = |state: &PlannerState| {
+            let character = state.characters().get(&character).unwrap();
+            character.assignments.suggested().copied().collect::<Vec<_>>()
+        };
+        suggest(&mut state, ON_PULL);
+        state.toggle_spell_enabled(character, CONVOKE);
+        let toggled = state.characters().clone();
+        suggest(&mut state, KNOCK_AOE);
+
+        assert_eq!(state.undo(), Some(PlannerEdit::ToggleSpell));
+        assert_eq!(suggested(&state), vec![(TRANQ_3M, KNOCK_AOE)]);
+        suggest(&mut state, ON_PULL);
+        assert_eq!(state.redo(), Some(PlannerEdit::ToggleSpell));
+        assert_eq!(state.characters(), &toggled);
+    }
+
+    #[test]
+    fn talents_can_be_undone() {
+        let (mut state, character) = base_assignability_setup();
+        let before = state.characters().clone();
+
+        state.set_character_talents(character, [("Restoration".to_string(), [740].into())].into());
+        assert_eq!(state.undo(), Some(PlannerEdit::SetTalents));
+
+        assert_eq!(state.characters(), &before);
+    }
+
+    #[test]
+    fn edits_are_recorded_after_a_panicking_edit() {
+        let (mut state, character) = base_assignability_setup();
+        let panicked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let general = CharacterUuid::general();
+            state.change_character_class(general, LocalizedString::constant("Druid"))
+        }));
+        assert!(panicked.is_err());
+
+        state.toggle_spell_enabled(character, CONVOKE);
+
+        assert_eq!(state.next_undo(), Some(PlannerEdit::ToggleSpell));
+    }
+
+    #[test]
+    fn share_code_round_trip() {
+        let (mut state, character) = base_assignability_setup();
+        let custom_attack = state.add_custom_attack(bloodlust());
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, custom_attack);
+        state.toggle_spell_enabled(character, TREE_OF_LIFE);
+
+        let mut loaded = PlannerState::new(state.fights.clone());
+        loaded.load_share_code(&state.share_code()).unwrap();
+
+        let without_uuids = |state: &PlannerState| {
+            let mut plan = state.saved_plan();
+            for character in &mut plan.characters {
+                character.uuid = CharacterUuid::general();
+                character.talents.clear();
+            }
+            plan
+        };
+        assert_eq!(without_uuids(&loaded), without_uuids(&state));
+        assert_eq!(
+            loaded.load_share_code("broken"),
+            Err(ShareCodeError::Encoding)
+        );
+        assert_eq!(loaded.characters().len(), 2);
+    }
+
+    #[test]
+    fn share_code_of_a_raid_fits_in_a_link() {
+        let (mut state, _) = base_assignability_setup();
+        for index in 0..19 {
+            let character = state.add_character(PlannerCharacterTemplate::Known {
+                name: format!("Healer{index}"),
+                realm: PlannerRealm {
+                    name: LocalizedString::constant("Draenor"),
+                    slug: "draenor".to_string(),
+                },
+                class: LocalizedString::constant("Druid"),
+                spec: LocalizedString::constant("Restoration"),
+            });
+            state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+            state.toggle_assignment(character, CONVOKE, AOE_80_PERCENT);
+            state.toggle_assignment(character, TREE_OF_LIFE, KNOCK_AOE);
+        }
+
+        assert!(state.share_code().len() < 2000);
+    }
+
+    #[test]
+    fn sheet_round_trip() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, AOE_80_PERCENT);
+        let sheet = state.export_sheet().unwrap();
+
+        let (mut imported, _) = base_assignability_setup();
+        let report = imported.import_sheet(&Sheet::from_csv(&sheet.to_csv()));
+
+        assert_eq!(report.imported, 2);
+        assert!(report.problems.is_empty());
+        assert_eq!(imported.export_sheet(), Some(sheet));
+    }
+
+    #[test]
+    fn import_sheet_reports_unknown_rows_and_columns() {
+        let (mut state, _) = base_assignability_setup();
+        let sheet = Sheet::from_csv("Time,Attack,Type,Nobody\n,,,Tranquility 3m\n00:01,Nothing,Red,x");
+
+        let report = state.import_sheet(&sheet);
+
+        assert_eq!(report.imported, 0);
+        assert_eq!(
+            report.problems,
+            vec![
+                SheetImportProblem::UnknownCharacter { column: 3, name: "Nobody".to_string() },
+                SheetImportProblem::UnknownAttack {
+                    row: 2,
+                    name: "Nothing".to_string(),
+                    time: "00:01".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn import_exported_note() {
+        let (mut state, character) = base_assignability_setup();
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(character, CONVOKE, AOE_80_PERCENT);
+        let export = state.export().unwrap();
+
+        let (mut imported, character) = base_assignability_setup();
+        let report = imported.import_note(&export);
+
+        assert_eq!(report, NoteImportReport { imported: 2, problems: vec![] });
+        let assignments = &imported.characters().get(&character).unwrap().assignments;
+        assert_eq!(assignments.get(TRANQ_3M, ON_PULL), AssignmentState::Locked);
+        assert_eq!(assignments.get(CONVOKE, AOE_80_PERCENT), AssignmentState::Locked);
+    }
+
+    #[test]
+    fn import_exported_general_assignments() {
+        let (mut state, character) = base_assignability_setup();
+        let healthstone = SpellUuid::new(uuid!("ac3f5e1b-4868-409c-9558-2c457ba9360b"));
+        state.toggle_assignment(character, TRANQ_3M, ON_PULL);
+        state.toggle_assignment(CharacterUuid::general(), healthstone, ON_PULL);
+        let export = state.export().unwrap();
+
+        let (mut imported, character) = base_assignability_setup();
+        let report = imported.import_note(&format!("{export}\n{{time:0:17}}Knock - {{everyone}} {{spell:1}}"));
+
+        assert_eq!(
+            report,
+            NoteImportReport {
+                imported: 2,
+                problems: vec![NoteImportProblem::UnknownSpell {
+                    line: export.lines().count() + 1,
+                    name: "{everyone}".to_string(),
+                    icon: NoteIcon::Spell(1),
+                }],
+            }
+        );
+        let general = imported.characters().get(&CharacterUuid::general()).unwrap();
+        assert_eq!(general.assignment_state(healthstone, ON_PULL), AssignmentState::Locked);
+        assert_eq!(
+            imported.characters().get(&character).unwrap().assignment_state(TRANQ_3M, ON_PULL),
+            AssignmentState::Locked
+        );
+    }
+
+    #[test]
+    fn import_hand_written_note_reports_unmatched() {
+        let (mut state, character) = base_assignability_setup();
+
+        let report = state.import_note(
+            "{time:0:04}Pull - test {spell:740}\n\
+            {time:0:17}Knock - Test {spell:108280}  Bob {spell:740}\n\
+            {time:9:00}Nothing - Test {spell:740}",
+        );
+
+        assert_eq!(report.imported, 1);
+        assert_eq!(
+            report.problems,
+            vec![
+                NoteImportProblem::UnknownSpell {
+                    line: 2,
+                    name: "Test".to_string(),
+                    icon: NoteIcon::Spell(108280),
+                },
+                NoteImportProblem::UnknownCharacter {
+                    line: 2,
+                    name: "Bob".to_string(),
+                },
+                NoteImportProblem::UnknownAttack {
+                    line: 3,
+                    time: "09:00".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            state.characters().get(&character).unwrap().assignment_state(TRANQ_3M, ON_PULL),
+            AssignmentState::Locked
+        );
+    }
+
     fn base_assignability_setup() -> (PlannerState, CharacterUuid) {
         let mut state = PlannerState::new(vec![Arc::new(Kazzara::mythic(
             Some(&mock_journal_instance()),
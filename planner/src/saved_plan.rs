@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use fight_domain::{Attack, AttackUuid, CharacterUuid, Spell, SpellOverride, SpellUuid};
+use i18n::LocalizedString;
+
+use crate::{Difficulty, PlannerRealm};
+
+/// Upgrades the JSON object of a plan by one version, `MIGRATIONS[0]` turns version 1 into 2.
+type Migration = fn(&mut Map<String, Value>);
+
+const MIGRATIONS: &[Migration] = &[];
+
+/// Version of the plans written by [`SavedPlan::to_json`].
+pub const SAVED_PLAN_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Everything the user entered for a plan, the rest is rebuilt from the fights and spec catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlan {
+    pub fight: SavedFight,
+    #[serde(default)]
+    pub custom_attacks: Vec<Attack>,
+    pub characters: Vec<SavedCharacter>,
+}
+
+/// Fights are looked up by encounter, custom fights without one by their name. Fights have no
+/// parameters the user can change yet, their phase timings are fixed, so nothing else is saved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFight {
+    pub encounter_id: i64,
+    pub name: String,
+    pub difficulty: Difficulty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedCharacter {
+    pub uuid: CharacterUuid,
+    pub name: Option<String>,
+    pub realm: Option<PlannerRealm>,
+    pub class: Option<LocalizedString>,
+    pub spec: Option<LocalizedString>,
+    #[serde(default)]
+    pub enabled_spells: BTreeSet<SpellUuid>,
+    #[serde(default)]
+    pub custom_spells: Vec<Spell>,
+    #[serde(default)]
+    pub spell_overrides: BTreeMap<SpellUuid, SpellOverride>,
+    #[serde(default)]
+    pub talents: BTreeMap<String, BTreeSet<u32>>,
+    #[serde(default)]
+    pub locked_assignments: Vec<(SpellUuid, AttackUuid)>,
+}
+
+#[derive(Debug)]
+pub enum SavedPlanError {
+    Json(serde_json::Error),
+    MissingVersion,
+    /// Saved by a newer version of the planner.
+    UnsupportedVersion(u32),
+}
+
+impl SavedPlan {
+    pub fn to_json(&self) -> String {
+        let Value::Object(mut plan) = serde_json::to_value(self).unwrap() else {
+            unreachable!("plans serialize to objects");
+        };
+        plan.insert("version".to_string(), SAVED_PLAN_VERSION.into());
+        Value::Object(plan).to_string()
+    }
+
+    /// Reads a plan of any version up to the current one.
+    pub fn from_json(json: &str) -> Result<Self, SavedPlanError> {
+        let plan = serde_json::from_str(json).map_err(SavedPlanError::Json)?;
+        let plan = migrate(plan, MIGRATIONS)?;
+        serde_json::from_value(Value::Object(plan)).map_err(SavedPlanError::Json)
+    }
+}
+
+fn migrate(
+    mut plan: Map<String, Value>,
+    migrations: &[Migration],
+) -> Result<Map<String, Value>, SavedPlanError> {
+    let version = plan
+        .remove("version")
+        .and_then(|version| version.as_u64())
+        .ok_or(SavedPlanError::MissingVersion)? as u32;
+    if version == 0 || version as usize > migrations.len() + 1 {
+        return Err(SavedPlanError::UnsupportedVersion(version));
+    }
+    for migration in &migrations[version as usize - 1..] {
+        migration(&mut plan);
+    }
+    Ok(plan)
+}
+
+impl Display for SavedPlanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SavedPlanError::Json(err) => write!(f, "Invalid plan: {err}"),
+            SavedPlanError::MissingVersion => write!(f, "Invalid plan: no version"),
+            SavedPlanError::UnsupportedVersion(version) => {
+                write!(f, "Plan version {version} is newer than this planner")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i18n::Locale;
+
+    use super::*;
+
+    fn plan() -> SavedPlan {
+        SavedPlan {
+            fight: SavedFight {
+                encounter_id: 2688,
+                name: "Kazzara, the Hellforged".to_string(),
+                difficulty: Difficulty::Heroic,
+            },
+            custom_attacks: vec![],
+            characters: vec![],
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let json = plan().to_json();
+
+        assert!(json.contains(&format!("\"version\":{SAVED_PLAN_VERSION}")));
+        assert_eq!(SavedPlan::from_json(&json).unwrap(), plan());
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let json = plan().to_json().replace(
+            &format!("\"version\":{SAVED_PLAN_VERSION}"),
+            &format!("\"version\":{}", SAVED_PLAN_VERSION + 1),
+        );
+
+        assert!(matches!(
+            SavedPlan::from_json(&json),
+            Err(SavedPlanError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            SavedPlan::from_json("{}"),
+            Err(SavedPlanError::MissingVersion)
+        ));
+    }
+
+    #[test]
+    fn constant_strings_are_written_once() {
+        let mut plan = plan();
+        plan.characters.push(SavedCharacter {
+            uuid: CharacterUuid::general(),
+            name: None,
+            realm: None,
+            class: Some(LocalizedString::constant("Druid")),
+            spec: None,
+            enabled_spells: BTreeSet::new(),
+            custom_spells: vec![],
+            spell_overrides: BTreeMap::new(),
+            talents: BTreeMap::new(),
+            locked_assignments: vec![],
+        });
+        let json = plan.to_json();
+
+        assert!(json.contains(r#""class":"Druid""#));
+        assert_eq!(SavedPlan::from_json(&json).unwrap(), plan);
+    }
+
+    #[test]
+    fn reads_version_1_plans() {
+        let plan = SavedPlan::from_json(include_str!("fixtures/saved_plan_v1.json")).unwrap();
+
+        assert_eq!(plan.fight.encounter_id, 2688);
+        assert_eq!(plan.fight.difficulty, Difficulty::Mythic);
+        assert_eq!(plan.custom_attacks[0].name.get(Locale::EnglishUnitedStates), "Bloodlust");
+        let character = &plan.characters[0];
+        assert_eq!(character.name.as_deref(), Some("Test"));
+        let spec = character.spec.as_ref().unwrap();
+        assert_eq!(spec.get(Locale::German), "Wiederherstellung");
+        assert_eq!(character.locked_assignments.len(), 1);
+        assert_eq!(character.spell_overrides.len(), 1);
+        assert_eq!(character.talents["Restoration"], BTreeSet::from([740]));
+        // the general character
+        assert_eq!(plan.characters[1].class, None);
+    }
+
+    #[test]
+    fn migrations_run_from_the_saved_version() {
+        let migrations: &[Migration] = &[
+            |plan| {
+                plan.insert("difficulty".to_string(), "Mythic".into());
+            },
+            |plan| {
+                let difficulty = plan.remove("difficulty").unwrap();
+                plan.insert(
+                    "fight".to_string(),
+                    serde_json::json!({ "difficulty": difficulty }),
+                );
+            },
+        ];
+        let version_1 = serde_json::from_str(r#"{ "version": 1 }"#).unwrap();
+        let version_2 =
+            serde_json::from_str(r#"{ "version": 2, "difficulty": "Heroic" }"#).unwrap();
+
+        assert_eq!(
+            Value::Object(migrate(version_1, migrations).unwrap()),
+            serde_json::json!({ "fight": { "difficulty": "Mythic" } })
+        );
+        assert_eq!(
+            Value::Object(migrate(version_2, migrations).unwrap()),
+            serde_json::json!({ "fight": { "difficulty": "Heroic" } })
+        );
+    }
+}
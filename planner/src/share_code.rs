@@ -0,0 +1,225 @@
+use std::fmt::{Display, Formatter};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Version of the binary layout, the first byte of every share code.
+pub const SHARE_CODE_VERSION: u8 = 1;
+// a share code never inflates to more than this, longer data is not a plan
+const MAX_DECOMPRESSED_SIZE: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareCodeError {
+    Encoding,
+    /// Made by a newer version of the planner.
+    UnsupportedVersion(u8),
+    Invalid,
+}
+
+/// Compresses and encodes the bytes for a URL.
+pub(crate) fn encode_share_code(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(compress_to_vec(bytes, 9))
+}
+
+pub(crate) fn decode_share_code(code: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let compressed = URL_SAFE_NO_PAD
+        .decode(code.trim().trim_start_matches('#'))
+        .map_err(|_| ShareCodeError::Encoding)?;
+    decompress_to_vec_with_limit(&compressed, MAX_DECOMPRESSED_SIZE)
+        .map_err(|_| ShareCodeError::Encoding)
+}
+
+/// Writes numbers as LEB128 varints and strings with their length in front.
+#[derive(Debug)]
+pub(crate) struct ShareCodeWriter {
+    bytes: Vec<u8>,
+}
+
+impl ShareCodeWriter {
+    pub fn new() -> Self {
+        Self {
+            bytes: vec![SHARE_CODE_VERSION],
+        }
+    }
+
+    pub fn number(&mut self, mut number: u64) {
+        loop {
+            let byte = (number & 0x7f) as u8;
+            number >>= 7;
+            if number == 0 {
+                self.bytes.push(byte);
+                return;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    pub fn string(&mut self, string: &str) {
+        self.number(string.len() as u64);
+        self.bytes.extend_from_slice(string.as_bytes());
+    }
+
+    /// For the rarely used parts of a plan, such as custom spells.
+    pub fn json(&mut self, value: &impl Serialize) {
+        self.string(&serde_json::to_string(value).unwrap());
+    }
+
+    /// One bit per flag, without a length: the reader knows how many flags there are.
+    pub fn flags(&mut self, flags: impl IntoIterator<Item = bool>) {
+        let mut byte = 0;
+        let mut count = 0;
+        for flag in flags {
+            byte |= u8::from(flag) << (count % 8);
+            count += 1;
+            if count % 8 == 0 {
+                self.bytes.push(std::mem::take(&mut byte));
+            }
+        }
+        if count % 8 != 0 {
+            self.bytes.push(byte);
+        }
+    }
+
+    pub fn finish(self) -> String {
+        encode_share_code(&self.bytes)
+    }
+}
+
+pub(crate) struct ShareCodeReader {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl ShareCodeReader {
+    pub fn new(code: &str) -> Result<Self, ShareCodeError> {
+        let bytes = decode_share_code(code)?;
+        match bytes.first() {
+            Some(&SHARE_CODE_VERSION) => Ok(Self { bytes, position: 1 }),
+            Some(&version) => Err(ShareCodeError::UnsupportedVersion(version)),
+            None => Err(ShareCodeError::Invalid),
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, ShareCodeError> {
+        let byte = *self
+            .bytes
+            .get(self.position)
+            .ok_or(ShareCodeError::Invalid)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    pub fn number(&mut self) -> Result<u64, ShareCodeError> {
+        let mut number = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            number |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(number);
+            }
+        }
+        Err(ShareCodeError::Invalid)
+    }
+
+    /// A number used to index into something with `len` elements.
+    pub fn index(&mut self, len: usize) -> Result<usize, ShareCodeError> {
+        let index = self.number()? as usize;
+        if index < len {
+            Ok(index)
+        } else {
+            Err(ShareCodeError::Invalid)
+        }
+    }
+
+    pub fn string(&mut self) -> Result<String, ShareCodeError> {
+        let len = self.number()? as usize;
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(ShareCodeError::Invalid)?;
+        let string = std::str::from_utf8(&self.bytes[self.position..end])
+            .map_err(|_| ShareCodeError::Invalid)?
+            .to_string();
+        self.position = end;
+        Ok(string)
+    }
+
+    pub fn json<T: DeserializeOwned>(&mut self) -> Result<T, ShareCodeError> {
+        serde_json::from_str(&self.string()?).map_err(|_| ShareCodeError::Invalid)
+    }
+
+    pub fn flags(&mut self, count: usize) -> Result<Vec<bool>, ShareCodeError> {
+        let mut flags = Vec::with_capacity(count);
+        let mut byte = 0;
+        for index in 0..count {
+            if index % 8 == 0 {
+                byte = self.byte()?;
+            }
+            flags.push(byte & (1 << (index % 8)) != 0);
+        }
+        Ok(flags)
+    }
+}
+
+impl Display for ShareCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::Encoding => write!(f, "The link is incomplete or damaged"),
+            ShareCodeError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "The link was made by a newer planner (version {version})"
+                )
+            }
+            ShareCodeError::Invalid => write!(f, "The link does not contain a plan"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut writer = ShareCodeWriter::new();
+        writer.number(300);
+        writer.string("Kazzara, the Hellforged");
+        writer.flags([true, false, false, true, true, false, true, false, true]);
+        writer.number(u64::MAX);
+
+        let code = writer.finish();
+        let mut reader = ShareCodeReader::new(&code).unwrap();
+
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(reader.number(), Ok(300));
+        assert_eq!(reader.string().unwrap(), "Kazzara, the Hellforged");
+        assert_eq!(
+            reader.flags(9),
+            Ok(vec![
+                true, false, false, true, true, false, true, false, true
+            ])
+        );
+        assert_eq!(reader.number(), Ok(u64::MAX));
+        assert_eq!(reader.number(), Err(ShareCodeError::Invalid));
+    }
+
+    #[test]
+    fn rejects_other_versions_and_garbage() {
+        assert_eq!(
+            ShareCodeReader::new(&encode_share_code(&[SHARE_CODE_VERSION + 1])).err(),
+            Some(ShareCodeError::UnsupportedVersion(SHARE_CODE_VERSION + 1))
+        );
+        assert_eq!(
+            ShareCodeReader::new("not a plan").err(),
+            Some(ShareCodeError::Encoding)
+        );
+    }
+}
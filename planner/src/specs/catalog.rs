@@ -0,0 +1,599 @@
+use uuid::{uuid, Uuid};
+
+pub struct ClassData {
+    pub name: &'static str,
+    pub default_spec: &'static str,
+    /// Spells every spec of the class has access to, listed after the spec spells.
+    pub spells: &'static [SpellData],
+    pub specs: &'static [SpecData],
+}
+
+pub struct SpecData {
+    /// Battle.net playable specialization id.
+    pub id: u64,
+    pub name: &'static str,
+    pub spells: &'static [SpellData],
+}
+
+pub struct SpellData {
+    pub uuid: Uuid,
+    pub name: &'static str,
+    pub icon_text: Option<&'static str>,
+    pub spell_id: u32,
+    pub power: f64,
+    pub charges: usize,
+    /// (minutes, seconds)
+    pub cooldown: (u64, u64),
+    /// (minutes, seconds) of casting or channeling
+    pub cast_time: (u64, u64),
+    pub exclusive_with: &'static [u32],
+    /// Talent spell ids that all need to be selected for the spell to be enabled by a loadout,
+    /// spells without talents are left alone.
+    pub talents: &'static [u32],
+    /// Talent spell ids that disable the spell when selected, used to pick between variants.
+    pub without_talents: &'static [u32],
+    pub enabled: bool,
+    pub minor: bool,
+}
+
+impl SpellData {
+    const fn major(uuid: Uuid, name: &'static str, spell_id: u32, cooldown: (u64, u64)) -> Self {
+        Self {
+            uuid,
+            name,
+            icon_text: None,
+            spell_id,
+            power: 1.0,
+            charges: 1,
+            cooldown,
+            cast_time: (0, 1),
+            exclusive_with: &[],
+            talents: &[],
+            without_talents: &[],
+            enabled: true,
+            minor: false,
+        }
+    }
+
+    const fn minor(uuid: Uuid, name: &'static str, spell_id: u32, cooldown: (u64, u64)) -> Self {
+        Self {
+            power: 0.5,
+            minor: true,
+            ..Self::major(uuid, name, spell_id, cooldown)
+        }
+    }
+
+    const fn icon_text(self, icon_text: &'static str) -> Self {
+        Self {
+            icon_text: Some(icon_text),
+            ..self
+        }
+    }
+
+    const fn power(self, power: f64) -> Self {
+        Self { power, ..self }
+    }
+
+    const fn cast_time(self, cast_time: (u64, u64)) -> Self {
+        Self { cast_time, ..self }
+    }
+
+    const fn exclusive_with(self, exclusive_with: &'static [u32]) -> Self {
+        Self {
+            exclusive_with,
+            ..self
+        }
+    }
+
+    const fn talents(self, talents: &'static [u32]) -> Self {
+        Self { talents, ..self }
+    }
+
+    const fn without_talents(self, without_talents: &'static [u32]) -> Self {
+        Self {
+            without_talents,
+            ..self
+        }
+    }
+
+    const fn disabled(self) -> Self {
+        Self {
+            enabled: false,
+            ..self
+        }
+    }
+}
+
+const fn spec(id: u64, name: &'static str) -> SpecData {
+    SpecData {
+        id,
+        name,
+        spells: &[],
+    }
+}
+
+pub const CLASSES: &[ClassData] = &[
+    ClassData {
+        name: "Death Knight",
+        default_spec: "Blood",
+        spells: &[
+            SpellData::minor(
+                uuid!("8052a299-9964-416a-8571-aba502db711d"),
+                "Anti-Magic Zone",
+                51052,
+                (2, 0),
+            )
+            .talents(&[51052]),
+            SpellData::minor(
+                uuid!("f6207491-bce5-46db-928d-2bd3a98de9a0"),
+                "Abomination Limb",
+                315443,
+                (2, 0),
+            )
+            .talents(&[383269])
+            .power(0.0)
+            .disabled(),
+        ],
+        specs: &[spec(250, "Blood"), spec(251, "Frost"), spec(252, "Unholy")],
+    },
+    ClassData {
+        name: "Demon Hunter",
+        default_spec: "Vengeance",
+        spells: &[SpellData::minor(
+            uuid!("b6030ff4-5386-41ca-b653-a89a7a4fa39d"),
+            "Darkness",
+            196718,
+            (3, 0),
+        )
+        .talents(&[196718])],
+        specs: &[spec(577, "Havoc"), spec(581, "Vengeance")],
+    },
+    ClassData {
+        name: "Druid",
+        default_spec: "Restoration",
+        spells: &[SpellData::minor(
+            uuid!("e6f6d9c2-0d51-463c-ac51-52403c678a00"),
+            "Stampeding Roar",
+            288826,
+            (1, 0),
+        )
+        .talents(&[106898])
+        .power(0.0)
+        .disabled()],
+        specs: &[
+            spec(102, "Balance"),
+            spec(103, "Feral"),
+            spec(104, "Guardian"),
+            SpecData {
+                id: 105,
+                name: "Restoration",
+                spells: &[
+                    SpellData::major(
+                        uuid!("43d4698a-6d7a-4afe-bc21-5b175988e5e0"),
+                        "Tranquility 3m",
+                        740,
+                        (3, 0),
+                    )
+                    .talents(&[740])
+                    .without_talents(&[197073])
+                    .icon_text("3m"),
+                    SpellData::major(
+                        uuid!("c3f8a190-2701-4bf7-87af-91ffb5ca969b"),
+                        "Tranquility 2m",
+                        740,
+                        (2, 0),
+                    )
+                    .talents(&[740, 197073])
+                    .icon_text("2m"),
+                    SpellData::major(
+                        uuid!("b8603366-ab57-413e-b6a9-a3c37af87a1c"),
+                        "Convoke the Spirits",
+                        323764,
+                        (1, 0),
+                    )
+                    .cast_time((0, 4))
+                    .talents(&[391528])
+                    .exclusive_with(&[33891])
+                    .disabled(),
+                    SpellData::major(
+                        uuid!("e561b6ff-077b-4ae5-97c7-395eab1062ef"),
+                        "Incarnation: Tree of Life",
+                        33891,
+                        (3, 0),
+                    )
+                    .talents(&[33891])
+                    .exclusive_with(&[323764])
+                    .disabled(),
+                    SpellData::major(
+                        uuid!("876d7838-af9e-4e05-b012-60c2655d539d"),
+                        "Flourish",
+                        197721,
+                        (1, 30),
+                    )
+                    .talents(&[197721])
+                    .disabled(),
+                ],
+            },
+        ],
+    },
+    ClassData {
+        name: "Evoker",
+        default_spec: "Preservation",
+        spells: &[SpellData::minor(
+            uuid!("0449e975-0ad0-4360-ab83-d9b0a1f3baae"),
+            "Zephyr",
+            374227,
+            (2, 0),
+        )
+        .talents(&[374227])],
+        specs: &[
+            spec(1467, "Devastation"),
+            SpecData {
+                id: 1468,
+                name: "Preservation",
+                spells: &[
+                    SpellData::major(
+                        uuid!("1a72ab43-fedc-4711-b067-139aae294a9b"),
+                        "Stasis",
+                        370537,
+                        (1, 30),
+                    )
+                    .talents(&[370537]),
+                    SpellData::major(
+                        uuid!("e1415788-f257-4bf0-abea-2a60867de272"),
+                        "Dream Flight",
+                        359816,
+                        (2, 0),
+                    )
+                    .talents(&[359816]),
+                    SpellData::major(
+                        uuid!("275ac361-4f7a-4ebd-8c8f-76a1f4d52225"),
+                        "Rewind 3m",
+                        363534,
+                        (3, 0),
+                    )
+                    // Temporal Artificer
+                    .talents(&[363534, 381922])
+                    .icon_text("3m"),
+                    SpellData::major(
+                        uuid!("a4cbc956-603d-48f1-8a62-b0d9b390842b"),
+                        "Rewind 4m",
+                        363534,
+                        (4, 0),
+                    )
+                    .talents(&[363534])
+                    .without_talents(&[381922])
+                    .icon_text("4m"),
+                ],
+            },
+            spec(1473, "Augmentation"),
+        ],
+    },
+    ClassData {
+        name: "Hunter",
+        default_spec: "Beast Mastery",
+        spells: &[SpellData::minor(
+            uuid!("ba8d20c5-5ee0-4b5d-88ad-923b1b31fb5d"),
+            "Survival of the Fittest",
+            264735,
+            (3, 0),
+        )
+        .talents(&[264735])
+        .power(0.0)
+        .disabled()],
+        specs: &[
+            spec(253, "Beast Mastery"),
+            spec(254, "Marksmanship"),
+            spec(255, "Survival"),
+        ],
+    },
+    ClassData {
+        name: "Mage",
+        default_spec: "Arcane",
+        spells: &[SpellData::minor(
+            uuid!("d5b87323-334a-4efb-8c35-facdf36e6afd"),
+            "Mass Barrier",
+            414660,
+            (2, 0),
+        )
+        .talents(&[414660])
+        .power(0.25)
+        .disabled()],
+        specs: &[spec(62, "Arcane"), spec(63, "Fire"), spec(64, "Frost")],
+    },
+    ClassData {
+        name: "Monk",
+        default_spec: "Mistweaver",
+        spells: &[
+            SpellData::minor(
+                uuid!("41ac9ddc-79a8-4d75-8ad8-34c4463dfb93"),
+                "Leg Sweep",
+                119381,
+                (1, 0),
+            )
+            .power(0.0)
+            .disabled(),
+            SpellData::minor(
+                uuid!("80545081-253b-4bf7-84a0-8f9f33aeb183"),
+                "Ring of Peace",
+                116844,
+                (0, 45),
+            )
+            .talents(&[116844])
+            .power(0.0)
+            .disabled(),
+        ],
+        specs: &[
+            spec(268, "Brewmaster"),
+            spec(269, "Windwalker"),
+            SpecData {
+                id: 270,
+                name: "Mistweaver",
+                spells: &[
+                    SpellData::major(
+                        uuid!("48f8a538-33fd-48e6-8e16-1bafe9c7fe41"),
+                        "Revival",
+                        115310,
+                        (2, 45),
+                    ),
+                    SpellData::major(
+                        uuid!("38aa07c2-6628-47f6-8bba-a558698676a9"),
+                        "Invoke Yu'lon 1m",
+                        322118,
+                        (1, 0),
+                    )
+                    .talents(&[322118, 388212])
+                    .icon_text("1m"),
+                    SpellData::major(
+                        uuid!("60ec61e4-d8f4-4b28-82c4-5c4511018122"),
+                        "Invoke Yu'lon ~3m",
+                        322118,
+                        (2, 30),
+                    )
+                    .talents(&[322118])
+                    .without_talents(&[388212])
+                    .icon_text("3m")
+                    .disabled(),
+                    SpellData::major(
+                        uuid!("9622b096-bbeb-40c4-879e-a2c392403cda"),
+                        "Invoke Chi-Ji 1m",
+                        325197,
+                        (1, 0),
+                    )
+                    .talents(&[325197, 388212])
+                    .icon_text("1m")
+                    .disabled(),
+                    SpellData::major(
+                        uuid!("05912352-ef9f-42cb-8ce3-48d91ab4dc6c"),
+                        "Invoke Chi-Ji ~3m",
+                        325197,
+                        (2, 30),
+                    )
+                    .talents(&[325197])
+                    .without_talents(&[388212])
+                    .icon_text("3m"),
+                ],
+            },
+        ],
+    },
+    ClassData {
+        name: "Paladin",
+        default_spec: "Holy",
+        spells: &[SpellData::minor(
+            uuid!("2d842679-8c0f-4aa5-8cab-f08e7cb8883b"),
+            "Blessing of Sacrifice",
+            6940,
+            (2, 0),
+        )
+        .talents(&[6940])
+        .power(0.25)
+        .disabled()],
+        specs: &[
+            SpecData {
+                id: 65,
+                name: "Holy",
+                spells: &[
+                    SpellData::major(
+                        uuid!("06f9702f-af0e-4732-9045-6644668d3d75"),
+                        "Aura Mastery",
+                        31821,
+                        (3, 0),
+                    )
+                    .talents(&[31821]),
+                    SpellData::major(
+                        uuid!("e08621bd-333a-4931-afa4-930ffb9e0e1a"),
+                        "Avenging Wrath",
+                        31884,
+                        (2, 0),
+                    )
+                    .talents(&[31884]),
+                    SpellData::major(
+                        uuid!("ca932f28-9e66-418e-90c4-64e96b019cf8"),
+                        "Divine Toll",
+                        304971,
+                        (1, 0),
+                    )
+                    .talents(&[375576])
+                    .disabled(),
+                ],
+            },
+            spec(66, "Protection"),
+            spec(70, "Retribution"),
+        ],
+    },
+    ClassData {
+        name: "Priest",
+        default_spec: "Holy",
+        spells: &[],
+        specs: &[
+            SpecData {
+                id: 256,
+                name: "Discipline",
+                spells: &[
+                    SpellData::major(
+                        uuid!("fa224fb1-b7bb-4ae9-b527-2f328b10963f"),
+                        "Power Word: Barrier",
+                        62618,
+                        (3, 0),
+                    )
+                    .talents(&[62618]),
+                    SpellData::major(
+                        uuid!("b95796a0-ae3d-42f1-aa28-c5d1d35ddbee"),
+                        "Evangelism",
+                        246287,
+                        (1, 30),
+                    )
+                    .talents(&[246287]),
+                    SpellData::major(
+                        uuid!("6e032ea5-b285-415a-9b87-c19d384450b9"),
+                        "Rapture",
+                        47536,
+                        (1, 30),
+                    )
+                    .talents(&[47536])
+                    .disabled(),
+                ],
+            },
+            SpecData {
+                id: 257,
+                name: "Holy",
+                spells: &[
+                    SpellData::major(
+                        uuid!("ee38dcfe-55c5-4506-807b-228993e01fe9"),
+                        "Holy Word: Salvation",
+                        265202,
+                        (5, 0),
+                    )
+                    .talents(&[265202]),
+                    SpellData::major(
+                        uuid!("090e4af5-833a-4174-b360-f22721a1ff60"),
+                        "Divine Hymn",
+                        64843,
+                        (3, 0),
+                    )
+                    .cast_time((0, 8))
+                    .talents(&[64843]),
+                    SpellData::major(
+                        uuid!("c3cebe5e-c0a0-4dd5-8f06-298c1df8621e"),
+                        "Apotheosis",
+                        200183,
+                        (2, 0),
+                    )
+                    .talents(&[200183])
+                    .disabled(),
+                ],
+            },
+            SpecData {
+                id: 258,
+                name: "Shadow",
+                spells: &[SpellData::minor(
+                    uuid!("80594828-1888-46f9-9763-fe421c93e58d"),
+                    "Vampiric Embrace",
+                    15286,
+                    (2, 0),
+                )
+                .talents(&[15286])],
+            },
+        ],
+    },
+    ClassData {
+        name: "Rogue",
+        default_spec: "Outlaw",
+        spells: &[SpellData::minor(
+            uuid!("8f2ecfdd-bd57-4a26-91cb-3580a3d5e9fe"),
+            "Shroud of Concealment",
+            114018,
+            (6, 0),
+        )
+        .power(0.0)
+        .disabled()],
+        specs: &[
+            spec(259, "Assassination"),
+            spec(260, "Outlaw"),
+            spec(261, "Subtlety"),
+        ],
+    },
+    ClassData {
+        name: "Shaman",
+        default_spec: "Restoration",
+        spells: &[
+            SpellData::minor(
+                uuid!("773095ca-e648-4fc0-9e26-d9afb8f32600"),
+                "Ancestral Guidance",
+                108281,
+                (2, 0),
+            )
+            .talents(&[108281]),
+            SpellData::minor(
+                uuid!("75a98518-cbf2-4325-a40a-7ba5747ff527"),
+                "Wind Rush Totem",
+                192077,
+                (2, 0),
+            )
+            .talents(&[192077])
+            .power(0.0)
+            .disabled(),
+        ],
+        specs: &[
+            spec(262, "Elemental"),
+            spec(263, "Enhancement"),
+            SpecData {
+                id: 264,
+                name: "Restoration",
+                spells: &[
+                    SpellData::major(
+                        uuid!("cfc494b5-92ec-48d2-96b1-cf98993a8f14"),
+                        "Healing Tide Totem",
+                        108280,
+                        (3, 0),
+                    )
+                    .talents(&[108280]),
+                    SpellData::major(
+                        uuid!("19d5fcbc-1cb7-48db-852e-202c23a30a0e"),
+                        "Spirit Link Totem",
+                        98008,
+                        (3, 0),
+                    )
+                    .talents(&[98008]),
+                    SpellData::major(
+                        uuid!("d0944411-372f-4c77-8ba8-bc49ffe1ed7d"),
+                        "Ascendance",
+                        114052,
+                        (3, 0),
+                    )
+                    .talents(&[114052]),
+                ],
+            },
+        ],
+    },
+    ClassData {
+        name: "Warlock",
+        default_spec: "Destruction",
+        spells: &[SpellData::minor(
+            uuid!("773284e9-ae2c-4b64-afee-412659acedde"),
+            "Shadowfury",
+            30283,
+            (1, 0),
+        )
+        .talents(&[30283])
+        .power(0.0)
+        .disabled()],
+        specs: &[
+            spec(265, "Affliction"),
+            spec(266, "Demonology"),
+            spec(267, "Destruction"),
+        ],
+    },
+    ClassData {
+        name: "Warrior",
+        default_spec: "Protection",
+        spells: &[SpellData::minor(
+            uuid!("717c2202-9d5c-4c11-ac7c-0e24bf173aec"),
+            "Rallying Cry",
+            97462,
+            (3, 0),
+        )
+        .talents(&[97462])],
+        specs: &[spec(71, "Arms"), spec(72, "Fury"), spec(73, "Protection")],
+    },
+];
@@ -1,12 +1,13 @@
 use ordered_float::NotNan;
 
 use fight_domain::{FromMinutesSeconds, Identifier, Lookup, Spell, SpellUuid, TimeStep};
+use i18n::LocalizedString;
 use uuid::uuid;
 
 pub fn spells() -> Lookup<Spell> {
     [
         Spell {
-            name: "Personals".to_string(),
+            name: LocalizedString::constant("Personals"),
             icon_text: None,
             identifier: Identifier::Spell(71),
             power: NotNan::new(0.5 * (1.0 / 60.0)).unwrap(),
@@ -19,7 +20,7 @@ pub fn spells() -> Lookup<Spell> {
             minor: true,
         },
         Spell {
-            name: "Healthstone".to_string(),
+            name: LocalizedString::constant("Healthstone"),
             icon_text: None,
             identifier: Identifier::Icon("warlock_healthstone".to_string(), 538745),
             power: NotNan::new(0.25 * (1.0 / 60.0)).unwrap(),
@@ -32,7 +33,7 @@ pub fn spells() -> Lookup<Spell> {
             minor: true,
         },
         Spell {
-            name: "Healing Potion".to_string(),
+            name: LocalizedString::constant("Healing Potion"),
             icon_text: None,
             identifier: Identifier::Icon("inv_10_alchemy_bottle_shape4_red".to_string(), 4497595),
             power: NotNan::new(0.25 * (1.0 / 60.0)).unwrap(),
@@ -45,7 +46,7 @@ pub fn spells() -> Lookup<Spell> {
             minor: true,
         },
         Spell {
-            name: "Fury Of The Aspects".to_string(),
+            name: LocalizedString::constant("Fury Of The Aspects"),
             icon_text: None,
             identifier: Identifier::Spell(390386),
             power: NotNan::new(0.0).unwrap(),
@@ -58,7 +59,7 @@ pub fn spells() -> Lookup<Spell> {
             minor: true,
         },
         Spell {
-            name: "Gateway".to_string(),
+            name: LocalizedString::constant("Gateway"),
             icon_text: None,
             identifier: Identifier::Spell(111771),
             power: NotNan::new(0.0).unwrap(),
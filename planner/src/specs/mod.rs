@@ -1,50 +1,311 @@
-use fight_domain::{Lookup, Spell};
+use std::collections::BTreeSet;
 
-pub mod death_knight;
-pub mod demon_hunter;
-pub mod druid_restoration;
-pub mod evoker_preservation;
+use ordered_float::NotNan;
+
+use fight_domain::{FromMinutesSeconds, Identifier, Lookup, Spell, SpellUuid, TimeStep};
+use i18n::LocalizedString;
+
+use crate::specs::catalog::{ClassData, SpellData, CLASSES};
+
+mod catalog;
 pub mod general;
-pub mod monk_mistweaver;
-pub mod paladin_holy;
-pub mod priest_discipline;
-pub mod priest_holy;
-pub mod priest_shadow;
-pub mod shaman_restoration;
-pub mod warrior;
 
+fn class_data(class: &str) -> Option<&'static ClassData> {
+    CLASSES.iter().find(|c| c.name == class)
+}
+
+fn to_spell(data: &SpellData) -> Spell {
+    Spell {
+        name: LocalizedString::constant(data.name),
+        icon_text: data.icon_text.map(str::to_string),
+        identifier: Identifier::Spell(data.spell_id),
+        power: NotNan::new(data.power).unwrap(),
+        charges: data.charges,
+        cooldown: TimeStep::mm_ss(data.cooldown.0, data.cooldown.1),
+        cast_time: TimeStep::mm_ss(data.cast_time.0, data.cast_time.1),
+        exclusive_with: data
+            .exclusive_with
+            .iter()
+            .map(|id| Identifier::Spell(*id))
+            .collect(),
+        uuid: SpellUuid::new(data.uuid),
+        enabled: data.enabled,
+        minor: data.minor,
+    }
+}
+
+/// Spec spells followed by the spells shared by the whole class.
 pub fn spells_for_spec(class: &str, spec: &str) -> Lookup<Spell> {
-    match (class, spec) {
-        ("Death Knight", _) => death_knight::spells(),
-        ("Demon Hunter", _) => demon_hunter::spells(),
-        ("Druid", "Restoration") => druid_restoration::spells(),
-        ("Evoker", "Preservation") => evoker_preservation::spells(),
-        ("Monk", "Mistweaver") => monk_mistweaver::spells(),
-        ("Paladin", "Holy") => paladin_holy::spells(),
-        ("Priest", "Discipline") => priest_discipline::spells(),
-        ("Priest", "Holy") => priest_holy::spells(),
-        ("Priest", "Shadow") => priest_shadow::spells(),
-        ("Shaman", "Restoration") => shaman_restoration::spells(),
-        ("Warrior", _) => warrior::spells(),
-        _ => Lookup::default(),
-    }
-}
-
-pub fn default_spec_for_class(class: &str) -> Option<&str> {
-    match class {
-        "Death Knight" => Some("Blood"),
-        "Demon Hunter" => Some("Vengeance"),
-        "Druid" => Some("Restoration"),
-        "Evoker" => Some("Preservation"),
-        "Hunter" => Some("Beast Mastery"),
-        "Mage" => Some("Protection"),
-        "Monk" => Some("Mistweaver"),
-        "Paladin" => Some("Holy"),
-        "Priest" => Some("Holy"),
-        "Rogue" => Some("Outlaw"),
-        "Shaman" => Some("Restoration"),
-        "Warlock" => Some("Destruction"),
-        "Warrior" => Some("Protection"),
-        _ => None,
-    }
-}
\ No newline at end of file
+    let Some(class) = class_data(class) else {
+        return Lookup::default();
+    };
+    let Some(spec) = class.specs.iter().find(|s| s.name == spec) else {
+        return Lookup::default();
+    };
+
+    spec.spells
+        .iter()
+        .chain(class.spells)
+        .map(to_spell)
+        .collect()
+}
+
+/// Enabled state of the spec and class spells that depend on talents, for the given selected
+/// talent spell ids.
+pub fn spells_enabled_by_talents(
+    class: &str,
+    spec: &str,
+    talents: &BTreeSet<u32>,
+) -> Vec<(SpellUuid, bool)> {
+    let Some(class) = class_data(class) else {
+        return vec![];
+    };
+    let Some(spec) = class.specs.iter().find(|s| s.name == spec) else {
+        return vec![];
+    };
+
+    spec.spells
+        .iter()
+        .chain(class.spells)
+        .filter(|s| !s.talents.is_empty())
+        .map(|s| {
+            let enabled = s.talents.iter().all(|t| talents.contains(t))
+                && !s.without_talents.iter().any(|t| talents.contains(t));
+            (SpellUuid::new(s.uuid), enabled)
+        })
+        .collect()
+}
+
+pub fn specs_for_class(class: &str) -> Vec<&'static str> {
+    class_data(class)
+        .map(|c| c.specs.iter().map(|s| s.name).collect())
+        .unwrap_or_default()
+}
+
+pub fn default_spec_for_class(class: &str) -> Option<&'static str> {
+    class_data(class).map(|c| c.default_spec)
+}
+
+/// Name of the spec with the Battle.net playable specialization id.
+pub fn spec_name(id: u64) -> Option<&'static str> {
+    CLASSES
+        .iter()
+        .flat_map(|c| c.specs.iter())
+        .find(|s| s.id == id)
+        .map(|s| s.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn cached_index(file: &str) -> serde_json::Value {
+        let path = format!(
+            "{}/../auto-battle-net/cache/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            file
+        );
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn all_spells() -> impl Iterator<Item = &'static SpellData> {
+        CLASSES.iter().flat_map(|c| {
+            c.spells
+                .iter()
+                .chain(c.specs.iter().flat_map(|s| s.spells.iter()))
+        })
+    }
+
+    #[test]
+    fn catalog_covers_every_playable_specialization() {
+        let index = cached_index("_data_wow_playable-specialization_index.json");
+        let mut expected = index["character_specializations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| {
+                (
+                    s["id"].as_u64().unwrap(),
+                    s["name"]["en_US"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort();
+
+        let mut actual = CLASSES
+            .iter()
+            .flat_map(|c| c.specs.iter().map(|s| (s.id, s.name.to_string())))
+            .collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn catalog_covers_every_playable_class() {
+        let index = cached_index("_data_wow_playable-class_index.json");
+        let mut expected = index["classes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"]["en_US"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        expected.sort();
+
+        let mut actual = CLASSES.iter().map(|c| c.name).collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn default_specs_belong_to_their_class() {
+        for class in CLASSES {
+            assert!(
+                specs_for_class(class.name).contains(&class.default_spec),
+                "{} has no spec {}",
+                class.name,
+                class.default_spec
+            );
+        }
+        assert_eq!(default_spec_for_class("Mage"), Some("Arcane"));
+        assert_eq!(default_spec_for_class("Unknown"), None);
+    }
+
+    #[test]
+    fn spell_uuids_are_unique() {
+        let mut seen = HashSet::new();
+        for spell in all_spells() {
+            assert!(seen.insert(spell.uuid), "duplicate uuid for {}", spell.name);
+        }
+    }
+
+    #[test]
+    fn spells_are_valid() {
+        let spell_ids = all_spells().map(|s| s.spell_id).collect::<HashSet<_>>();
+        for spell in all_spells() {
+            assert!(spell.charges >= 1, "{} has no charges", spell.name);
+            assert!(
+                spell.cooldown.0 * 60 + spell.cooldown.1 > 0,
+                "{} has no cooldown",
+                spell.name
+            );
+            assert!(
+                spell.cast_time.0 * 60 + spell.cast_time.1 > 0,
+                "{} has no cast time",
+                spell.name
+            );
+            assert!(
+                spell.power.is_finite() && spell.power >= 0.0,
+                "{} has invalid power",
+                spell.name
+            );
+            for talent in spell.without_talents {
+                assert!(
+                    !spell.talents.contains(talent),
+                    "{} requires and excludes talent {}",
+                    spell.name,
+                    talent
+                );
+            }
+            for exclusive in spell.exclusive_with {
+                assert!(
+                    spell_ids.contains(exclusive),
+                    "{} is exclusive with unknown spell {}",
+                    spell.name,
+                    exclusive
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn talents_pick_spell_variants() {
+        let tranq_3m = SpellUuid::new(uuid::uuid!("43d4698a-6d7a-4afe-bc21-5b175988e5e0"));
+        let tranq_2m = SpellUuid::new(uuid::uuid!("c3f8a190-2701-4bf7-87af-91ffb5ca969b"));
+        let convoke = SpellUuid::new(uuid::uuid!("b8603366-ab57-413e-b6a9-a3c37af87a1c"));
+
+        let states = spells_enabled_by_talents("Druid", "Restoration", &[740, 197073, 391528].into());
+        assert!(states.contains(&(tranq_3m, false)));
+        assert!(states.contains(&(tranq_2m, true)));
+        assert!(states.contains(&(convoke, true)));
+
+        let states = spells_enabled_by_talents("Druid", "Restoration", &[740].into());
+        assert!(states.contains(&(tranq_3m, true)));
+        assert!(states.contains(&(tranq_2m, false)));
+        assert!(states.contains(&(convoke, false)));
+    }
+
+    #[test]
+    fn talents_never_enable_two_variants_of_a_spell() {
+        for class in CLASSES {
+            for spec in class.specs {
+                let spells = spec.spells.iter().chain(class.spells).collect::<Vec<_>>();
+                for (i, a) in spells.iter().enumerate() {
+                    for b in &spells[i + 1..] {
+                        if a.spell_id != b.spell_id || a.talents.is_empty() || b.talents.is_empty() {
+                            continue;
+                        }
+                        let excludes = |x: &SpellData, y: &SpellData| {
+                            x.without_talents.iter().any(|t| y.talents.contains(t))
+                        };
+                        assert!(
+                            excludes(a, b) || excludes(b, a),
+                            "{} and {} can both be enabled",
+                            a.name,
+                            b.name
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spells_of_several_specs_are_class_spells() {
+        for class in CLASSES {
+            for (i, a) in class.specs.iter().enumerate() {
+                for b in &class.specs[i + 1..] {
+                    for spell in a.spells {
+                        assert!(
+                            b.spells.iter().all(|other| other.spell_id != spell.spell_id),
+                            "{} is defined by {} and {}",
+                            spell.name,
+                            a.name,
+                            b.name
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spec_name_by_id() {
+        assert_eq!(spec_name(105), Some("Restoration"));
+        assert_eq!(spec_name(1468), Some("Preservation"));
+        assert_eq!(spec_name(0), None);
+    }
+
+    #[test]
+    fn spells_for_spec_includes_class_spells() {
+        let spells = spells_for_spec("Paladin", "Retribution");
+        assert!(spells
+            .iter()
+            .any(|s| s.identifier == Identifier::Spell(6940)));
+
+        let spells = spells_for_spec("Paladin", "Holy");
+        assert_eq!(
+            spells.iter().next().unwrap().identifier,
+            Identifier::Spell(31821)
+        );
+        assert!(spells
+            .iter()
+            .any(|s| s.identifier == Identifier::Spell(6940)));
+
+        assert!(spells_for_spec("Paladin", "Unknown").is_empty());
+        assert!(spells_for_spec("Unknown", "Holy").is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use itertools::Itertools;
+
+use crate::spreadsheet::Sheet;
+
+impl Sheet {
+    pub fn to_csv(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| quote(cell)).join(","))
+            .map(|line| line + "\r\n")
+            .collect()
+    }
+
+    /// Reads comma separated values, or semicolon separated ones as saved by spreadsheets in
+    /// locales with a decimal comma.
+    pub fn from_csv(text: &str) -> Self {
+        let text = text.trim_start_matches('\u{feff}');
+        let first_line = text.lines().next().unwrap_or_default();
+        let separator = if first_line.matches(';').count() > first_line.matches(',').count() {
+            ';'
+        } else {
+            ','
+        };
+
+        let mut rows = vec![];
+        let mut row = vec![];
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => quoted = !quoted,
+                c if c == separator && !quoted => row.push(std::mem::take(&mut cell)),
+                '\r' if !quoted => {}
+                '\n' if !quoted => {
+                    row.push(std::mem::take(&mut cell));
+                    rows.push(std::mem::take(&mut row));
+                }
+                c => cell.push(c),
+            }
+        }
+        if !cell.is_empty() || !row.is_empty() {
+            row.push(cell);
+            rows.push(row);
+        }
+        Sheet { rows }
+    }
+}
+
+fn quote(cell: &str) -> String {
+    if cell.contains([',', ';', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trip() {
+        let sheet = Sheet {
+            rows: vec![
+                vec!["Time".to_string(), "Attack".to_string(), "Test".to_string()],
+                vec![
+                    "00:03".to_string(),
+                    "Wings, \"Hellsteel\"".to_string(),
+                    "Locked".to_string(),
+                ],
+                vec![
+                    "00:10".to_string(),
+                    "Line\nbreak".to_string(),
+                    String::new(),
+                ],
+            ],
+        };
+
+        let csv = sheet.to_csv();
+
+        assert!(csv.contains("\"Wings, \"\"Hellsteel\"\"\""));
+        assert_eq!(Sheet::from_csv(&csv), sheet);
+    }
+
+    #[test]
+    fn reads_semicolon_separated_values() {
+        let sheet = Sheet::from_csv("Time;Attack;Test\n00:03;On Pull;x");
+
+        assert_eq!(sheet.rows[1], ["00:03", "On Pull", "x"]);
+    }
+}
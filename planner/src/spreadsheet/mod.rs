@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+
+mod csv;
+mod xlsx;
+
+pub const TIME_HEADER: &str = "Time";
+pub const ATTACK_HEADER: &str = "Attack";
+pub const TYPE_HEADER: &str = "Type";
+pub const LOCKED_CELL: &str = "Locked";
+pub const SUGGESTED_CELL: &str = "Suggested";
+/// Columns in front of the spell columns.
+pub const ATTACK_COLUMNS: usize = 3;
+
+/// Assignment grid as a spreadsheet. The first row holds the character of every spell column,
+/// the second row the spell names, and every following row one attack with its time, name and
+/// type colour.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sheet {
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Sheet {
+    pub fn cell(&self, row: usize, column: usize) -> &str {
+        self.rows
+            .get(row)
+            .and_then(|cells| cells.get(column))
+            .map(|cell| cell.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SheetCellState {
+    Locked,
+    Suggested,
+}
+
+impl SheetCellState {
+    /// Hand-edited sheets often just mark a cell with an `x`.
+    pub fn parse(cell: &str) -> Option<Self> {
+        if cell.eq_ignore_ascii_case(LOCKED_CELL) || cell.eq_ignore_ascii_case("x") {
+            Some(SheetCellState::Locked)
+        } else if cell.eq_ignore_ascii_case(SUGGESTED_CELL) {
+            Some(SheetCellState::Suggested)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetImportProblem {
+    UnknownAttack {
+        row: usize,
+        name: String,
+        time: String,
+    },
+    UnknownCharacter {
+        column: usize,
+        name: String,
+    },
+    UnknownSpell {
+        column: usize,
+        name: String,
+        spell: String,
+    },
+    NotAssignable {
+        row: usize,
+        column: usize,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SheetImportReport {
+    pub imported: usize,
+    pub problems: Vec<SheetImportProblem>,
+}
+
+impl Display for SheetImportProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // 1-based like the spreadsheet itself
+        match self {
+            SheetImportProblem::UnknownAttack { row, name, time } => {
+                write!(f, "Row {}: no attack {name} at {time}", row + 1)
+            }
+            SheetImportProblem::UnknownCharacter { column, name } => {
+                write!(f, "Column {}: no character named {name}", column + 1)
+            }
+            SheetImportProblem::UnknownSpell {
+                column,
+                name,
+                spell,
+            } => {
+                write!(f, "Column {}: {name} has no spell {spell}", column + 1)
+            }
+            SheetImportProblem::NotAssignable { row, column } => {
+                write!(
+                    f,
+                    "Row {}, column {}: conflicts with another assignment",
+                    row + 1,
+                    column + 1
+                )
+            }
+        }
+    }
+}
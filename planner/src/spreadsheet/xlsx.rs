@@ -0,0 +1,99 @@
+use std::io::Cursor;
+
+use calamine::{open_workbook_from_rs, Reader, Xlsx, XlsxError};
+use rust_xlsxwriter::{Color, Format, Workbook};
+
+use fight_domain::AttackType;
+
+use crate::spreadsheet::{Sheet, ATTACK_COLUMNS};
+
+impl Sheet {
+    /// Workbook with a single worksheet, attack rows filled with the colour of their type.
+    pub fn to_xlsx(&self) -> Result<Vec<u8>, rust_xlsxwriter::XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let header = Format::new().set_bold();
+        for (row, cells) in self.rows.iter().enumerate() {
+            let format = match AttackType::try_from(self.cell(row, ATTACK_COLUMNS - 1)) {
+                Ok(attack_type) => Format::new().set_background_color(Color::RGB(rgb(attack_type))),
+                Err(()) => header.clone(),
+            };
+            for (column, cell) in cells.iter().enumerate() {
+                worksheet.write_string_with_format(row as u32, column as u16, cell, &format)?;
+            }
+        }
+        workbook.save_to_buffer()
+    }
+
+    /// Reads the first worksheet of a workbook.
+    pub fn from_xlsx(bytes: &[u8]) -> Result<Self, XlsxError> {
+        let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))?;
+        let Some(range) = workbook.worksheet_range_at(0) else {
+            return Ok(Sheet::default());
+        };
+        let range = range?;
+        // the range starts at the first used cell, not at A1
+        let (first_row, first_column) = range.start().unwrap_or_default();
+        let padding = vec![String::new(); first_column as usize];
+        let rows = (0..first_row)
+            .map(|_| vec![])
+            .chain(range.rows().map(|cells| {
+                padding
+                    .iter()
+                    .cloned()
+                    .chain(cells.iter().map(|cell| cell.to_string()))
+                    .collect()
+            }))
+            .collect();
+        Ok(Sheet { rows })
+    }
+}
+
+// light colours of the spreadsheet palette, dark ones make the cells hard to read
+fn rgb(attack_type: AttackType) -> u32 {
+    match attack_type {
+        AttackType::RaidDamage => 0xF4CCCC,
+        AttackType::RaidDamageStacked => 0xFCE5CD,
+        AttackType::RotDamage => 0xFFF2CC,
+        AttackType::Movement => 0xD9EAD3,
+        AttackType::Dispels => 0xCFE2F3,
+        AttackType::Debuffs => 0xD9D2E9,
+        AttackType::Adds => 0xEAD1DC,
+        AttackType::Generic => 0xEFEFEF,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xlsx_round_trip() {
+        let sheet = Sheet {
+            rows: vec![
+                vec![
+                    "Time".to_string(),
+                    "Attack".to_string(),
+                    "Type".to_string(),
+                    "Test".to_string(),
+                ],
+                vec![
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    "Tranquility".to_string(),
+                ],
+                vec![
+                    "00:03".to_string(),
+                    "On Pull".to_string(),
+                    "Red".to_string(),
+                    "Locked".to_string(),
+                ],
+            ],
+        };
+
+        let bytes = sheet.to_xlsx().unwrap();
+
+        assert_eq!(Sheet::from_xlsx(&bytes).unwrap(), sheet);
+    }
+}
@@ -0,0 +1,234 @@
+use miniz_oxide::deflate::compress_to_vec;
+
+use crate::weak_aura::LuaValue;
+
+const SERIALIZATION_VERSION: u8 = 1;
+
+// type indices of LibSerialize when the type does not fit in the first byte
+const NUM_16_POS: u8 = 1;
+const NUM_24_POS: u8 = 3;
+const NUM_32_POS: u8 = 5;
+const NUM_64_POS: u8 = 7;
+const BOOL_T: u8 = 12;
+const BOOL_F: u8 = 13;
+const STR_8: u8 = 14;
+const TABLE_8: u8 = 17;
+const ARRAY_8: u8 = 20;
+
+// type indices of LibSerialize when the count is embedded in the first byte
+const EMBEDDED_STRING: u8 = 0;
+const EMBEDDED_TABLE: u8 = 1;
+const EMBEDDED_ARRAY: u8 = 2;
+
+const PRINTABLE: &[u8; 64] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789()";
+
+/// Serializes, compresses and encodes a table the way WeakAuras exports `!WA:2!` strings.
+pub fn encode_weak_aura(value: &LuaValue) -> String {
+    let serialized = lib_serialize(value);
+    let compressed = compress_to_vec(&serialized, 9);
+    format!("!WA:2!{}", encode_for_print(&compressed))
+}
+
+/// LibSerialize encoding of a value, without string or table references.
+pub fn lib_serialize(value: &LuaValue) -> Vec<u8> {
+    let mut out = vec![SERIALIZATION_VERSION];
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut Vec<u8>, value: &LuaValue) {
+    match value {
+        LuaValue::Bool(true) => out.push(BOOL_T << 3),
+        LuaValue::Bool(false) => out.push(BOOL_F << 3),
+        LuaValue::Int(number) => write_int(out, *number),
+        LuaValue::String(text) => {
+            write_count(out, EMBEDDED_STRING, STR_8, text.len());
+            out.extend_from_slice(text.as_bytes());
+        }
+        LuaValue::Array(values) => {
+            write_count(out, EMBEDDED_ARRAY, ARRAY_8, values.len());
+            for value in values {
+                write_value(out, value);
+            }
+        }
+        LuaValue::Table(entries) => {
+            write_count(out, EMBEDDED_TABLE, TABLE_8, entries.len());
+            for (key, value) in entries {
+                write_value(out, &LuaValue::String(key.clone()));
+                write_value(out, value);
+            }
+        }
+    }
+}
+
+fn write_int(out: &mut Vec<u8>, number: i64) {
+    if (0..128).contains(&number) {
+        // NNNN NNN1
+        out.push((number as u8) << 1 | 1);
+    } else if number.unsigned_abs() < 4096 {
+        // NNNN S100 followed by the upper 8 bits
+        let sign = if number < 0 { 8 } else { 0 };
+        let embedded = number.unsigned_abs() * 16 + sign + 4;
+        out.push((embedded % 256) as u8);
+        out.push((embedded / 256) as u8);
+    } else {
+        let negative = u8::from(number < 0);
+        let magnitude = number.unsigned_abs();
+        let (reader, bytes) = match magnitude {
+            0..=0xFFFF => (NUM_16_POS, 2),
+            0x1_0000..=0xFF_FFFF => (NUM_24_POS, 3),
+            0x100_0000..=0xFFFF_FFFF => (NUM_32_POS, 4),
+            _ => (NUM_64_POS, 7),
+        };
+        out.push((reader + negative) << 3);
+        write_big_endian(out, magnitude, bytes);
+    }
+}
+
+fn write_count(out: &mut Vec<u8>, embedded: u8, reader_8: u8, count: usize) {
+    if count < 16 {
+        // CCCC TT10
+        out.push((count as u8) << 4 | embedded << 2 | 2);
+    } else {
+        // the 16 and 24 bit variants follow the 8 bit one
+        let bytes = match count {
+            0..=0xFF => 1,
+            0x100..=0xFFFF => 2,
+            _ => 3,
+        };
+        out.push((reader_8 + bytes - 1) << 3);
+        write_big_endian(out, count as u64, bytes as usize);
+    }
+}
+
+fn write_big_endian(out: &mut Vec<u8>, number: u64, bytes: usize) {
+    out.extend_from_slice(&number.to_be_bytes()[8 - bytes..]);
+}
+
+/// `LibDeflate:EncodeForPrint`, 6 bits per printable character, least significant bits first.
+pub fn encode_for_print(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 4 / 3 + 4);
+    let chunks = bytes.chunks_exact(3);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        let cache = u32::from(chunk[0]) | u32::from(chunk[1]) << 8 | u32::from(chunk[2]) << 16;
+        for shift in [0, 6, 12, 18] {
+            out.push(PRINTABLE[(cache >> shift & 63) as usize] as char);
+        }
+    }
+
+    let mut cache = 0u32;
+    let mut bits = 0i32;
+    for byte in remainder {
+        cache |= u32::from(*byte) << bits;
+        bits += 8;
+    }
+    while bits > 0 {
+        out.push(PRINTABLE[(cache & 63) as usize] as char);
+        cache >>= 6;
+        bits -= 6;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use miniz_oxide::inflate::decompress_to_vec;
+
+    use super::*;
+
+    #[test]
+    fn serializes_embedded_and_long_values() {
+        assert_eq!(lib_serialize(&LuaValue::Int(5)), [1, 11]);
+        assert_eq!(lib_serialize(&LuaValue::Int(300)), [1, 0xC4, 0x12]);
+        assert_eq!(lib_serialize(&LuaValue::Int(-300)), [1, 0xCC, 0x12]);
+        assert_eq!(
+            lib_serialize(&LuaValue::Int(401316)),
+            [1, 24, 0x06, 0x1F, 0xA4]
+        );
+        assert_eq!(lib_serialize(&LuaValue::Bool(true)), [1, 96]);
+        assert_eq!(lib_serialize(&"m".into()), [1, 0x12, b'm']);
+        assert_eq!(
+            lib_serialize(&LuaValue::table([("m", "d".into())])),
+            [1, 0x16, 0x12, b'm', 0x12, b'd']
+        );
+        assert_eq!(
+            lib_serialize(&LuaValue::array([LuaValue::Int(1), LuaValue::Int(2)])),
+            [1, 0x2A, 3, 5]
+        );
+
+        let long = "a".repeat(20);
+        let serialized = lib_serialize(&long.as_str().into());
+        assert_eq!(serialized[..3], [1, STR_8 << 3, 20]);
+    }
+
+    #[test]
+    fn sizes_match_lib_serialize_readers() {
+        // version byte included, at the boundaries where LibSerialize switches readers
+        let sizes = [
+            (LuaValue::Bool(false), 2),
+            (LuaValue::Int(0), 2),
+            (LuaValue::Int(127), 2),
+            (LuaValue::Int(128), 3),
+            (LuaValue::Int(4095), 3),
+            (LuaValue::Int(-4095), 3),
+            (LuaValue::Int(4096), 4),
+            (LuaValue::Int(65535), 4),
+            (LuaValue::Int(65536), 5),
+            (LuaValue::Int(16777215), 5),
+            (LuaValue::Int(16777216), 6),
+            (LuaValue::Int(4294967295), 6),
+            (LuaValue::Int(4294967296), 9),
+            (LuaValue::Int(-4294967296), 9),
+            ("".into(), 2),
+            ("a".into(), 3),
+            ("a".repeat(15).as_str().into(), 17),
+            ("a".repeat(16).as_str().into(), 19),
+            ("a".repeat(256).as_str().into(), 260),
+            (LuaValue::Table(vec![]), 2),
+            (LuaValue::array([LuaValue::Int(1)]), 3),
+        ];
+        for (value, size) in sizes {
+            assert_eq!(lib_serialize(&value).len(), size, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn encodes_for_print() {
+        assert_eq!(encode_for_print(&[]), "");
+        assert_eq!(encode_for_print(&[0, 0, 0]), "aaaa");
+        assert_eq!(encode_for_print(&[255, 255, 255]), "))))");
+        assert_eq!(encode_for_print(&[1]), "ba");
+        assert_eq!(encode_for_print(&[1, 1]), "bea");
+    }
+
+    #[test]
+    fn weak_aura_string_inflates_back() {
+        let value = LuaValue::table([("m", "d".into()), ("v", LuaValue::Int(1421))]);
+
+        let encoded = encode_weak_aura(&value);
+
+        let printable = encoded.strip_prefix("!WA:2!").unwrap();
+        let compressed = decode_for_print(printable);
+        assert_eq!(
+            decompress_to_vec(&compressed).unwrap(),
+            lib_serialize(&value)
+        );
+    }
+
+    fn decode_for_print(text: &str) -> Vec<u8> {
+        let mut out = vec![];
+        let mut cache = 0u32;
+        let mut bits = 0;
+        for c in text.bytes() {
+            cache |= (PRINTABLE.iter().position(|p| *p == c).unwrap() as u32) << bits;
+            bits += 6;
+            while bits >= 8 {
+                out.push(cache as u8);
+                cache >>= 8;
+                bits -= 8;
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,48 @@
+/// Lua value as read by LibSerialize, enough to describe WeakAura tables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaValue {
+    Bool(bool),
+    Int(i64),
+    String(String),
+    Array(Vec<LuaValue>),
+    Table(Vec<(String, LuaValue)>),
+}
+
+impl LuaValue {
+    pub fn table<const N: usize>(entries: [(&str, LuaValue); N]) -> Self {
+        LuaValue::Table(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn array(values: impl IntoIterator<Item = LuaValue>) -> Self {
+        LuaValue::Array(values.into_iter().collect())
+    }
+}
+
+impl From<bool> for LuaValue {
+    fn from(value: bool) -> Self {
+        LuaValue::Bool(value)
+    }
+}
+
+impl From<i64> for LuaValue {
+    fn from(value: i64) -> Self {
+        LuaValue::Int(value)
+    }
+}
+
+impl From<&str> for LuaValue {
+    fn from(value: &str) -> Self {
+        LuaValue::String(value.to_string())
+    }
+}
+
+impl From<String> for LuaValue {
+    fn from(value: String) -> Self {
+        LuaValue::String(value)
+    }
+}
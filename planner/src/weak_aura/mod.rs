@@ -0,0 +1,245 @@
+use fight_domain::{AttackTimer, CleuEventType, TimeStep};
+use uuid::Uuid;
+
+pub use encoding::*;
+pub use lua_value::*;
+
+mod encoding;
+mod lua_value;
+
+/// Seconds a reminder shows up before its assignment.
+const LEAD_TIME: i32 = 5;
+const REMINDER_EVENT: &str = "COOLDOWN_PLANNER_REMINDER";
+const WEAK_AURAS_VERSION: &str = "5.12.0";
+const INTERNAL_VERSION: i64 = 70;
+// version of the transmission format, fixed by WeakAuras
+const TRANSMISSION_VERSION: i64 = 1421;
+
+/// One assignment to remind a player of at the time of its attack, general assignments have no
+/// player and remind everyone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeakAuraReminder {
+    pub player: Option<String>,
+    pub text: String,
+    pub timer: AttackTimer,
+}
+
+/// `!WA:2!` import string of a group with one text aura per reminder, loaded for its player.
+pub fn reminders_weak_aura(
+    name: &str,
+    encounter_id: Option<i64>,
+    reminders: &[WeakAuraReminder],
+) -> String {
+    let children = reminders
+        .iter()
+        .enumerate()
+        .map(|(index, reminder)| reminder_aura(name, encounter_id, index, reminder))
+        .collect::<Vec<_>>();
+    let child_ids = children
+        .iter()
+        .filter_map(|child| match child {
+            LuaValue::Table(entries) => entries
+                .iter()
+                .find(|(key, _)| key == "id")
+                .map(|(_, id)| id.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let group = LuaValue::table([
+        ("id", name.into()),
+        ("uid", uid().into()),
+        ("regionType", "dynamicgroup".into()),
+        ("internalVersion", INTERNAL_VERSION.into()),
+        ("controlledChildren", LuaValue::array(child_ids)),
+    ]);
+
+    encode_weak_aura(&LuaValue::table([
+        ("m", "d".into()),
+        ("d", group),
+        ("c", LuaValue::array(children)),
+        ("s", WEAK_AURAS_VERSION.into()),
+        ("v", TRANSMISSION_VERSION.into()),
+    ]))
+}
+
+fn reminder_aura(
+    group: &str,
+    encounter_id: Option<i64>,
+    index: usize,
+    reminder: &WeakAuraReminder,
+) -> LuaValue {
+    let uid = uid();
+    let mut load = vec![];
+    if let Some(player) = &reminder.player {
+        load.push(("use_name".to_string(), true.into()));
+        load.push(("name".to_string(), player.as_str().into()));
+    }
+    if let Some(encounter_id) = encounter_id {
+        load.push(("use_encounterid".to_string(), true.into()));
+        load.push(("encounterid".to_string(), encounter_id.to_string().into()));
+    }
+
+    let trigger = LuaValue::table([
+        ("type", "custom".into()),
+        ("custom_type", "event".into()),
+        ("custom_hide", "timed".into()),
+        ("duration", LEAD_TIME.to_string().into()),
+        ("events", trigger_events(&reminder.timer).into()),
+        ("custom", trigger_code(&uid, &reminder.timer).into()),
+    ]);
+
+    LuaValue::table([
+        (
+            "id",
+            format!("{group} - {} {}", reminder.text, index + 1).into(),
+        ),
+        ("uid", uid.into()),
+        ("parent", group.into()),
+        ("regionType", "text".into()),
+        ("displayText", reminder.text.as_str().into()),
+        ("internalVersion", INTERNAL_VERSION.into()),
+        (
+            "triggers",
+            LuaValue::array([LuaValue::table([
+                ("trigger", trigger),
+                ("untrigger", LuaValue::Table(vec![])),
+            ])]),
+        ),
+        ("load", LuaValue::Table(load)),
+    ])
+}
+
+fn trigger_events(timer: &AttackTimer) -> String {
+    match &timer.dynamic_trigger_cleu_event {
+        Some(cleu_event) => format!(
+            "ENCOUNTER_START ENCOUNTER_END CLEU:{} {REMINDER_EVENT}",
+            subevent(&cleu_event.r#type)
+        ),
+        None => format!("ENCOUNTER_START ENCOUNTER_END {REMINDER_EVENT}"),
+    }
+}
+
+/// Custom trigger that schedules the reminder on pull, or once the CLEU event happened the
+/// expected number of times. Timers of an earlier pull are ignored, they outlive a wipe.
+fn trigger_code(uid: &str, timer: &AttackTimer) -> String {
+    // aura_env is not the aura's own in timer callbacks, so it is kept in a local
+    let schedule = |delay: TimeStep, indent: &str| {
+        let delay = (delay.as_secs() - LEAD_TIME).max(0);
+        format!(
+            "local env, pull = aura_env, aura_env.pull\
+            \n{indent}C_Timer.After({delay}, function()\
+            \n{indent}    if env.pull == pull then\
+            \n{indent}        WeakAuras.ScanEvents(\"{REMINDER_EVENT}\", \"{uid}\")\
+            \n{indent}    end\
+            \n{indent}end)"
+        )
+    };
+
+    let (on_pull, on_combat_log) = match &timer.dynamic_trigger_cleu_event {
+        Some(cleu_event) => (
+            String::new(),
+            format!(
+                "\n    elseif event == \"COMBAT_LOG_EVENT_UNFILTERED\" and aura_env.pull and select(12, ...) == {spell} then\
+                \n        aura_env.count = (aura_env.count or 0) + 1\
+                \n        if aura_env.count == {counter} then\
+                \n            {schedule}\
+                \n        end",
+                spell = cleu_event.event,
+                counter = cleu_event.counter,
+                schedule = schedule(timer.dynamic_timer.unwrap_or(TimeStep::zero()), "            "),
+            ),
+        ),
+        None => (
+            format!("\n        {}", schedule(timer.static_timer(), "        ")),
+            String::new(),
+        ),
+    };
+
+    format!(
+        "function(event, ...)\
+        \n    if event == \"ENCOUNTER_START\" then\
+        \n        aura_env.count = 0\
+        \n        aura_env.pull = GetTime(){on_pull}\
+        \n    elseif event == \"ENCOUNTER_END\" then\
+        \n        aura_env.pull = nil{on_combat_log}\
+        \n    elseif event == \"{REMINDER_EVENT}\" then\
+        \n        return ... == \"{uid}\"\
+        \n    end\
+        \nend"
+    )
+}
+
+fn subevent(r#type: &CleuEventType) -> &'static str {
+    match r#type {
+        CleuEventType::SpellCastSuccess => "SPELL_CAST_SUCCESS",
+        CleuEventType::SpellCastStart => "SPELL_CAST_START",
+        CleuEventType::SpellAuraApplied => "SPELL_AURA_APPLIED",
+        CleuEventType::SpellAuraRemoved => "SPELL_AURA_REMOVED",
+    }
+}
+
+fn uid() -> String {
+    Uuid::new_v4().simple().to_string()[..11].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use fight_domain::{CleuEvent, FromMinutesSeconds};
+
+    use super::*;
+
+    #[test]
+    fn static_reminder_is_scheduled_on_pull() {
+        let timer = AttackTimer {
+            dynamic_timer: Some(TimeStep::mm_ss(1, 0)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            trigger_events(&timer),
+            "ENCOUNTER_START ENCOUNTER_END COOLDOWN_PLANNER_REMINDER"
+        );
+        let code = trigger_code("abc", &timer);
+        assert!(code.contains("C_Timer.After(55, function()"));
+        assert!(code.contains("aura_env.pull = GetTime()"));
+        assert!(code.contains("if env.pull == pull then"));
+        assert!(!code.contains("COMBAT_LOG_EVENT_UNFILTERED"));
+    }
+
+    #[test]
+    fn triggered_reminder_counts_cleu_events() {
+        let timer = AttackTimer {
+            phase_start: Some(TimeStep::mm_ss(1, 20)),
+            dynamic_timer: Some(TimeStep::mm_ss(0, 8)),
+            dynamic_trigger_cleu_event: Some(CleuEvent {
+                r#type: CleuEventType::SpellCastStart,
+                event: 401316,
+                counter: 2,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            trigger_events(&timer),
+            "ENCOUNTER_START ENCOUNTER_END CLEU:SPELL_CAST_START COOLDOWN_PLANNER_REMINDER"
+        );
+        let code = trigger_code("abc", &timer);
+        assert!(code.contains("select(12, ...) == 401316"));
+        assert!(code.contains("aura_env.count == 2"));
+        assert!(code.contains("C_Timer.After(3, function()"));
+    }
+
+    #[test]
+    fn export_is_a_weak_aura_string() {
+        let reminders = [WeakAuraReminder {
+            player: Some("Test".to_string()),
+            text: "Tranquility".to_string(),
+            timer: AttackTimer::default(),
+        }];
+
+        let export = reminders_weak_aura("Kazzara", Some(2688), &reminders);
+
+        assert!(export.starts_with("!WA:2!"));
+    }
+}
@@ -0,0 +1,28 @@
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Lets the browser save the bytes as a file, through a temporary link to a blob.
+pub fn download_file(file_name: &str, mime_type: &str, bytes: &[u8]) {
+    let parts = Array::of1(&Uint8Array::from(bytes));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let Ok(blob) = Blob::new_with_u8_array_sequence_and_options(&parts, &options) else {
+        web_sys::console::log_1(&"Failed to create blob".into());
+        return;
+    };
+    let Ok(url) = Url::create_object_url_with_blob(&blob) else {
+        web_sys::console::log_1(&"Failed to create object url".into());
+        return;
+    };
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let anchor = document
+        .create_element("a")
+        .unwrap()
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    let _ = Url::revoke_object_url(&url);
+}
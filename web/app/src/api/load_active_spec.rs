@@ -0,0 +1,56 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use fight_domain::CharacterUuid;
+use i18n::Locale;
+use planner::{DroppedAssignment, PlannerState};
+
+use crate::context::UserContext;
+use crate::serverfns::character_summary;
+
+/// Switches a known character to the spec they currently play according to Battle.net, adding the
+/// assignments it drops to `dropped_assignments`.
+pub fn load_active_spec(
+    planner: RwSignal<PlannerState>,
+    user: UserContext,
+    character_uuid: CharacterUuid,
+    dropped_assignments: RwSignal<Vec<DroppedAssignment>>,
+) {
+    let Some((name, realm)) = planner
+        .read_untracked()
+        .characters()
+        .get(&character_uuid)
+        .and_then(|c| c.name.clone().zip(c.realm.clone()))
+    else {
+        return;
+    };
+    spawn_local(async move {
+        let region = user.region.await;
+        let summary = character_summary(
+            name.get(Locale::EnglishUnitedStates).to_string(),
+            realm.slug,
+            region,
+        )
+        .await;
+        match summary {
+            Ok(summary) => {
+                let Some(active_spec) = summary.active_spec else {
+                    return;
+                };
+                let dropped = planner.try_update(|planner| {
+                    if planner.characters().contains_key(&character_uuid) {
+                        planner.change_character_spec(character_uuid, active_spec.name)
+                    } else {
+                        vec![]
+                    }
+                });
+                let dropped = dropped.unwrap_or_default();
+                if !dropped.is_empty() {
+                    dropped_assignments
+                        .update(|dropped_assignments| dropped_assignments.extend(dropped));
+                }
+            }
+            Err(e) => web_sys::console::log_1(&format!("failed to load active spec: {e}").into()),
+        }
+    });
+}
@@ -0,0 +1,44 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use fight_domain::CharacterUuid;
+use i18n::Locale;
+use planner::PlannerState;
+
+use crate::context::UserContext;
+use crate::serverfns::character_talents;
+
+/// Fetches the active talent loadouts of a known character and enables the spells they select.
+pub fn load_character_talents(
+    planner: RwSignal<PlannerState>,
+    user: UserContext,
+    character_uuid: CharacterUuid,
+    refresh: bool,
+) {
+    let Some((name, realm)) = planner
+        .read_untracked()
+        .characters()
+        .get(&character_uuid)
+        .and_then(|c| c.name.clone().zip(c.realm.clone()))
+    else {
+        return;
+    };
+    spawn_local(async move {
+        let region = user.region.await;
+        let talents = character_talents(
+            name.get(Locale::EnglishUnitedStates).to_string(),
+            realm.slug,
+            region,
+            refresh,
+        )
+        .await;
+        match talents {
+            Ok(talents) => planner.update(|planner| {
+                if planner.characters().contains_key(&character_uuid) {
+                    planner.set_character_talents(character_uuid, talents);
+                }
+            }),
+            Err(e) => web_sys::console::log_1(&format!("failed to load talents: {e}").into()),
+        }
+    });
+}
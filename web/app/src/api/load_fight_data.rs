@@ -0,0 +1,23 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use planner::PlannerState;
+
+use crate::context::aberrus_fights;
+use crate::serverfns::aberrus;
+
+/// Swaps in the fights with their journal data, so attacks get their names and spell ids.
+pub fn load_fight_data(planner: RwSignal<PlannerState>) {
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match aberrus().await {
+                Ok(aberrus) => {
+                    planner.update(|planner| planner.replace_fights(aberrus_fights(Some(&aberrus))))
+                }
+                Err(e) => {
+                    web_sys::console::log_1(&format!("failed to load fight data: {e}").into())
+                }
+            }
+        });
+    });
+}
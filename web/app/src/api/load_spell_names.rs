@@ -0,0 +1,40 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use planner::PlannerState;
+
+use crate::context::UserContext;
+use crate::serverfns::spell_names;
+
+/// Follows the locale of the user and translates the spells of the plan whenever new ones show
+/// up, e.g. after a spec change.
+pub fn load_spell_names(planner: RwSignal<PlannerState>, user: UserContext) {
+    Effect::new(move |_| {
+        spawn_local(async move {
+            let locale = user.locale.await;
+            planner.update(|planner| planner.set_locale(locale));
+        });
+    });
+
+    let spell_ids = Memo::new(move |_| planner.with(|planner| planner.spell_ids()));
+    Effect::new(move |loaded: Option<Vec<u32>>| {
+        let mut loaded = loaded.unwrap_or_default();
+        let missing = spell_ids
+            .get()
+            .into_iter()
+            .filter(|id| !loaded.contains(id))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            loaded.extend(&missing);
+            spawn_local(async move {
+                match spell_names(missing).await {
+                    Ok(names) => planner.update(|planner| planner.set_spell_names(names)),
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("failed to load spell names: {e}").into())
+                    }
+                }
+            });
+        }
+        loaded
+    });
+}
@@ -1,7 +1,19 @@
+mod download_file;
 mod icon_url;
+mod load_active_spec;
+mod load_character_talents;
+mod load_fight_data;
+mod load_spell_names;
 mod write_to_clipboard;
 mod use_optimizer;
+mod use_undo_shortcuts;
 
+pub use download_file::download_file;
 pub use icon_url::icon_url;
+pub use load_active_spec::load_active_spec;
+pub use load_character_talents::load_character_talents;
+pub use load_fight_data::load_fight_data;
+pub use load_spell_names::load_spell_names;
 pub use write_to_clipboard::write_to_clipboard;
-pub use use_optimizer::use_optimizer;
\ No newline at end of file
+pub use use_optimizer::use_optimizer;
+pub use use_undo_shortcuts::use_undo_shortcuts;
\ No newline at end of file
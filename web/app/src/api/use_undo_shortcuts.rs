@@ -0,0 +1,41 @@
+use leptos::ev;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use crate::context::use_planner;
+
+/// Ctrl+Z undoes the last edit of the plan, Ctrl+Y and Ctrl+Shift+Z redo it. Text fields keep
+/// their own undo.
+pub fn use_undo_shortcuts() {
+    let planner = use_planner();
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        if !(ev.ctrl_key() || ev.meta_key()) {
+            return;
+        }
+        let in_text_field = ev
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+            .is_some_and(|element| {
+                element.is_content_editable()
+                    || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA")
+            });
+        if in_text_field {
+            return;
+        }
+        let redo = match ev.key().to_lowercase().as_str() {
+            "z" => ev.shift_key(),
+            "y" => true,
+            _ => return,
+        };
+        ev.prevent_default();
+        planner.update(|planner| {
+            if redo {
+                planner.redo();
+            } else {
+                planner.undo();
+            }
+        });
+    });
+    on_cleanup(move || handle.remove());
+}
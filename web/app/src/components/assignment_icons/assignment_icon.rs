@@ -5,7 +5,7 @@ use fight_domain::{AttackUuid, CharacterUuid, Spell};
 use optimizer::{AssignmentState};
 
 use crate::api::icon_url;
-use crate::context::use_planner;
+use crate::context::{use_planner, use_read_only};
 
 #[component]
 pub fn AssignmentIcon(
@@ -16,6 +16,7 @@ pub fn AssignmentIcon(
     is_assignable: bool,
 ) -> impl IntoView {
     let planner = use_planner();
+    let read_only = use_read_only();
 
     let src = icon_url(spell.identifier);
     let is_assigned = assignment_state != AssignmentState::Unassigned;
@@ -54,7 +55,7 @@ pub fn AssignmentIcon(
                     return;
                 }
                 clicked.set(true);
-                if is_assignable {
+                if is_assignable && !read_only {
                     planner.update(|planner| {
                         planner.toggle_assignment(character_uuid, spell.uuid, attack_uuid);
                     });
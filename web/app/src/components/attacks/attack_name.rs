@@ -1,18 +1,22 @@
 use leptos::prelude::*;
-use fight_domain::AttackUuid;
+use fight_domain::{AttackUuid, Identifier};
+
+use crate::api::icon_url;
 
 #[component]
-pub fn AttackName(uuid: AttackUuid, name: String) -> impl IntoView {
+pub fn AttackName(uuid: AttackUuid, name: String, identifier: Option<Identifier>) -> impl IntoView {
     let column = "attack_name";
     let row = format!("attack_{}", uuid);
+    let src = identifier.and_then(icon_url);
 
     view! {
         <div
-            class="justify-center items-center"
+            class="flex justify-center items-center gap-1"
             style:grid-column-start=column
             style:grid-row-start=row
         >
+            {src.map(|src| view! { <img class="h-5 w-5 rounded-sm" src=src alt="" /> })}
             {name}
         </div>
     }
-}
\ No newline at end of file
+}
@@ -16,7 +16,7 @@ pub fn Attacks() -> impl IntoView {
                     let attack = planner.attacks().get(&uuid)?.clone();
 
                     Some(view! {
-                        <AttackName uuid name={attack.name.clone()} />
+                        <AttackName uuid name={attack.name.get(planner.locale()).to_owned()} identifier={attack.identifier.clone()} />
                         <AttackTime uuid timer={attack.timer.clone()} />
                     })
                 }
@@ -3,11 +3,12 @@ use leptos::prelude::*;
 use planner::PlannerCharacter;
 use crate::components::character_headers::character_header_player_editing::CharacterHeaderPlayerEditing;
 use crate::components::character_headers::character_header_player_normal::CharacterHeaderPlayerNormal;
+use crate::context::use_read_only;
 
 #[component]
 pub fn CharacterHeaderPlayer(character: PlannerCharacter) -> impl IntoView {
     // if there is no name, instantly go into edit mode
-    let editing = RwSignal::new(character.name.is_none());
+    let editing = RwSignal::new(character.name.is_none() && !use_read_only());
     view! {
         <div class="flex flex-col h-full">
             {move || if editing.get() { Either::Left(view! {
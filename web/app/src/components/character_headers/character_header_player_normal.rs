@@ -3,16 +3,20 @@ use planner::PlannerCharacter;
 use crate::components::character_headers::class_color_bar::ClassColorBar;
 use crate::components::character_headers::name_normal::NameNormal;
 use crate::components::character_headers::spec_rows::SpecRows;
+use crate::api::load_character_talents;
 use crate::components::icons::x_mark::XMark;
-use crate::context::use_planner;
+use crate::context::{use_planner, use_read_only, UserContext};
 
 #[component]
 pub fn CharacterHeaderPlayerNormal(editing: RwSignal<bool>, character: PlannerCharacter) -> impl IntoView {
     let planner = use_planner();
+    let user = use_context::<UserContext>().unwrap();
     let character_uuid = character.uuid;
+    let is_known = character.realm.is_some();
+    let read_only = use_read_only();
     view! {
         <div class="w-fit flex flex-row items-start border-l-2 border-transparent cursor-text">
-            <div class="hover:text-white">
+            <div class="hover:text-white" class=("invisible", read_only)>
                 <XMark {..}
                     on:mousedown=move |ev| {
                         if ev.button() != 0 {
@@ -28,7 +32,7 @@ pub fn CharacterHeaderPlayerNormal(editing: RwSignal<bool>, character: PlannerCh
             <div
                 class="hover:text-white"
                 on:mousedown=move |ev| {
-                    if ev.button() != 0 {
+                    if ev.button() != 0 || read_only {
                         return;
                     }
                     editing.set(true)
@@ -39,6 +43,20 @@ pub fn CharacterHeaderPlayerNormal(editing: RwSignal<bool>, character: PlannerCh
                     realm=character.realm.as_ref().map(|r| r.name.clone())
                 />
             </div>
+            <Show when=move || is_known && !read_only>
+                <button
+                    class="w-5 text-sm hover:text-white"
+                    title="Refresh talents"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        load_character_talents(planner, user, character_uuid, true);
+                    }
+                >
+                    <div class="fa-solid fa-rotate"></div>
+                </button>
+            </Show>
         </div>
         <SpecRows character />
     }
@@ -4,14 +4,15 @@ use leptos::html::Input;
 use leptos::prelude::*;
 
 use fight_domain::CharacterUuid;
-use i18n::Locale;
+use i18n::{Locale, LocalizedString};
 use planner::{PlannerCharacterTemplate, PlannerRealm};
 use planner::fuzzy_search::{fuzzy_score, fuzzy_search};
 use planner::specs::default_spec_for_class;
 
+use crate::api::{load_active_spec, load_character_talents};
 use crate::components::autocomplete::*;
 use crate::components::character_headers::class_color_bar::ClassColorBar;
-use crate::context::{GuildRoster, RosterEntry, use_class_spec_index, use_planner, UserContext};
+use crate::context::{GuildRoster, RosterEntry, use_class_spec_index, use_dropped_assignments, use_planner, UserContext};
 use crate::misc::localized_string_with_context::LocalizedStringWithContext;
 use crate::serverfns::realms_for_character;
 
@@ -21,6 +22,7 @@ pub fn NameEditing(
     editing: RwSignal<bool>,
 ) -> impl IntoView {
     let planner = use_planner();
+    let dropped_assignments = use_dropped_assignments();
 
     let query = RwSignal::new(String::new());
     let selection = RwSignal::new(0usize);
@@ -75,24 +77,34 @@ pub fn NameEditing(
                             Suspend(async move {
                                 let spec_name = default_spec_for_class(entry.class.get(Locale::EnglishUnitedStates));
                                 let specs = csi.specs_for_class(entry.class.clone()).await;
-                                let full_spec_name = specs.iter().find(|s| Some(s.get(Locale::EnglishUnitedStates)) == spec_name).cloned();
+                                // placeholder until the active spec is known
+                                let full_spec_name = specs
+                                    .iter()
+                                    .find(|s| Some(s.get(Locale::EnglishUnitedStates)) == spec_name)
+                                    .cloned()
+                                    .or_else(|| spec_name.map(LocalizedString::constant));
 
                                 view! {
                                     <AutocompleteItem
                                         on_select=Callback::new({
                                             let entry = entry.clone();
                                             move |_| {
-                                                let full_spec_name = full_spec_name.clone();
-                                                planner.update(|planner| {
-                                                    let new_uuid = planner.replace_character(current_character_uuid, PlannerCharacterTemplate::Known {
+                                                let Some(full_spec_name) = full_spec_name.clone() else {
+                                                    return;
+                                                };
+                                                let replaced = planner.try_update(|planner| {
+                                                    planner.replace_character(current_character_uuid, PlannerCharacterTemplate::Known {
                                                         name: entry.name.clone(),
                                                         realm: entry.realm.clone(),
                                                         class: entry.class.clone(),
-                                                    });
-                                                    if let Some(full_spec_name) = full_spec_name {
-                                                        planner.change_character_spec(new_uuid, full_spec_name.clone());
-                                                    }
+                                                        spec: full_spec_name,
+                                                    })
                                                 });
+                                                if let Some((new_uuid, dropped)) = replaced {
+                                                    dropped_assignments.set(dropped);
+                                                    load_active_spec(planner, user, new_uuid, dropped_assignments);
+                                                    load_character_talents(planner, user, new_uuid, false);
+                                                }
                                             }
                                         })
                                         let:highlighted
@@ -2,11 +2,13 @@ use leptos::prelude::*;
 use planner::PlannerCharacter;
 use crate::components::character_headers::spec_rows_normal::SpecRowsNormal;
 use crate::components::character_headers::spec_rows_picking::SpecRowsPicking;
+use crate::context::use_read_only;
 
 #[component]
 pub fn SpecRows(character: PlannerCharacter) -> impl IntoView {
+    let read_only = use_read_only();
     // if there is no class (or spec??), instantly go into picking mode
-    let picking = RwSignal::new(character.class.is_none() || character.spec.is_none());
+    let picking = RwSignal::new(!read_only && (character.class.is_none() || character.spec.is_none()));
 
     let character_class = character.class.clone();
     let character_spec = character.spec.clone();
@@ -26,20 +28,22 @@ pub fn SpecRows(character: PlannerCharacter) -> impl IntoView {
                     />
                 })
         }
-        <div
-            class=("invisible", Signal::derive(move || {
-                !picking.get()
-            }))
-            class=("-z-50", Signal::derive(move || {
-                !picking.get()
-            }))
-        >
-            <SpecRowsPicking
-                picking=picking
-                character_uuid=character.uuid
-                character_class=character_class.clone()
-                character_spec=character_spec.clone()
-            />
-        </div>
+        {(!read_only).then(|| view! {
+            <div
+                class=("invisible", Signal::derive(move || {
+                    !picking.get()
+                }))
+                class=("-z-50", Signal::derive(move || {
+                    !picking.get()
+                }))
+            >
+                <SpecRowsPicking
+                    picking=picking
+                    character_uuid=character.uuid
+                    character_class=character_class.clone()
+                    character_spec=character_spec.clone()
+                />
+            </div>
+        })}
     }
 }
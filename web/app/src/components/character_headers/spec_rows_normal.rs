@@ -2,14 +2,16 @@ use leptos::prelude::*;
 use i18n::LocalizedString;
 use crate::components::character_headers::spec_row::SpecRow;
 use crate::components::icons::chevron::ChevronDirection;
+use crate::context::use_read_only;
 
 #[component]
 pub fn SpecRowsNormal(picking: RwSignal<bool>, character_class: LocalizedString, character_spec: Option<LocalizedString>) -> impl IntoView {
+    let read_only = use_read_only();
     view! {
         <div
             class="border-2 border-transparent -mt-1"
             on:mousedown=move |ev| {
-                if ev.button() != 0 {
+                if ev.button() != 0 || read_only {
                     return;
                 }
                 picking.set(true);
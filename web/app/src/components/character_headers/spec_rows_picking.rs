@@ -4,11 +4,12 @@ use i18n::LocalizedString;
 use itertools::Itertools;
 use crate::components::character_headers::spec_row::SpecRow;
 use crate::components::icons::chevron::ChevronDirection;
-use crate::context::{use_class_spec_index, use_planner};
+use crate::context::{use_class_spec_index, use_dropped_assignments, use_planner};
 
 #[component]
 pub fn SpecRowsPicking(picking: RwSignal<bool>, character_uuid: CharacterUuid, character_class: Option<LocalizedString>, character_spec: Option<LocalizedString>) -> impl IntoView {
     let planner = use_planner();
+    let dropped_assignments = use_dropped_assignments();
     let csi = use_class_spec_index();
     let character_class = character_class.clone();
     let classes_and_specs = if let Some(character_class) = character_class {
@@ -17,12 +18,20 @@ pub fn SpecRowsPicking(picking: RwSignal<bool>, character_uuid: CharacterUuid, c
         AsyncDerived::new(move || {
             let character_spec = character_spec.clone();
             let character_class = character_class.clone();
+            // specs the character has a talent loadout for come right after the current one
+            let character = planner.with(|planner| planner.characters().get(&character_uuid).cloned());
             async move {
                 specs
                     .await
                     .into_iter()
                     .map(|s| (character_class.clone(), Some(s)))
-                    .sorted_by_key(|(_, spec)| spec != &character_spec)
+                    .sorted_by_key(|(_, spec)| {
+                        let played = spec
+                            .as_ref()
+                            .zip(character.as_ref())
+                            .is_some_and(|(spec, character)| character.plays_spec(spec));
+                        (spec != &character_spec, !played)
+                    })
                     .collect::<Vec<_>>()
             }
         })
@@ -66,14 +75,16 @@ pub fn SpecRowsPicking(picking: RwSignal<bool>, character_uuid: CharacterUuid, c
                                         if ev.button() != 0 {
                                             return;
                                         }
-                                        planner.update(|planner| {
+                                        let dropped = planner.try_update(|planner| {
                                             if let Some(spec) = &entry.2 {
-                                                planner.change_character_spec(character_uuid, spec.clone());
+                                                let dropped = planner.change_character_spec(character_uuid, spec.clone());
                                                 picking.set(false);
+                                                dropped
                                             } else {
-                                                planner.change_character_class(character_uuid, entry.1.clone());
+                                                planner.change_character_class(character_uuid, entry.1.clone())
                                             }
-                                        })
+                                        });
+                                        dropped_assignments.set(dropped.unwrap_or_default());
                                     }
                                 />
                             </div>
@@ -3,11 +3,12 @@ use leptos::prelude::*;
 use fight_domain::{CharacterUuid, Spell};
 
 use crate::api::icon_url;
-use crate::context::use_planner;
+use crate::context::{use_planner, use_read_only};
 
 #[component]
 pub fn CharacterSpellToggle(character_uuid: CharacterUuid, spell: Spell) -> impl IntoView {
     let planner = use_planner();
+    let read_only = use_read_only();
 
     let src = icon_url(spell.identifier);
     let tag = spell.icon_text.unwrap_or("\u{00A0}".to_string());
@@ -35,7 +36,7 @@ pub fn CharacterSpellToggle(character_uuid: CharacterUuid, spell: Spell) -> impl
             class=("hover:brightness-100", !spell.enabled)
             style:background-image=background_image
             on:mousedown=move |ev| {
-                if ev.button() != 0 {
+                if ev.button() != 0 || read_only {
                     return;
                 }
                 planner.update(|planner| {
@@ -1,16 +1,18 @@
 use leptos::prelude::*;
 
-use crate::context::use_planner;
+use crate::context::{use_planner, use_read_only};
 
 #[component]
 pub fn LockButton() -> impl IntoView {
     let planner = use_planner();
+    let read_only = use_read_only();
 
     view! {
         <button
             class="h-12 w-12 transform rounded-md border-2 border-green-950 bg-green-600 text-2xl text-green-950 \
             transition-transform duration-75 \
             hover:bg-green-500 focus-visible:outline focus-visible:outline-1 focus-visible:outline-offset-2 focus-visible:outline-slate-300 active:scale-95"
+            class=("hidden", read_only)
             on:mousedown=move |ev| {
                 if ev.button() != 0 {
                     return;
@@ -1,9 +1,13 @@
 use leptos::prelude::*;
 
 mod copy_button;
+mod copy_personal_button;
+mod copy_weak_aura_button;
 mod lock_button;
 
 use copy_button::*;
+use copy_personal_button::*;
+use copy_weak_aura_button::*;
 use fight_domain::Lookup;
 use lock_button::*;
 use std::sync::Arc;
@@ -20,6 +24,8 @@ pub fn CornerButtons(
             style:grid-row-end="character_spells"
         >
             <CopyButton/>
+            <CopyPersonalButton/>
+            <CopyWeakAuraButton/>
             <LockButton/>
         </div>
     }
@@ -0,0 +1,260 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use ordered_float::NotNan;
+
+use fight_domain::{AttackType, AttackUuid, CleuEvent, CleuEventType, TimeStep};
+use planner::CustomAttackTemplate;
+
+use crate::components::inputs::timer_input::TimerInput;
+use crate::context::use_planner;
+
+const ATTACK_TYPES: [(AttackType, &str); 8] = [
+    (AttackType::RaidDamage, "Raid Damage"),
+    (AttackType::RaidDamageStacked, "Raid Damage (Stacked)"),
+    (AttackType::RotDamage, "Rot Damage"),
+    (AttackType::Movement, "Movement"),
+    (AttackType::Dispels, "Dispels"),
+    (AttackType::Debuffs, "Debuffs"),
+    (AttackType::Adds, "Adds"),
+    (AttackType::Generic, "Generic"),
+];
+
+const TRIGGER_TYPES: [CleuEventType; 4] = [
+    CleuEventType::SpellCastStart,
+    CleuEventType::SpellCastSuccess,
+    CleuEventType::SpellAuraApplied,
+    CleuEventType::SpellAuraRemoved,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttackEdit {
+    Name(String),
+    Timer(TimeStep),
+    Trigger(Option<CleuEventType>),
+    TriggerEvent(u64),
+    TriggerCounter(u64),
+    Type(AttackType),
+    Power(NotNan<f64>),
+}
+
+fn apply_edit(current: &CustomAttackTemplate, edit: AttackEdit) -> CustomAttackTemplate {
+    let mut template = current.clone();
+    match edit {
+        AttackEdit::Name(name) => template.name = name,
+        AttackEdit::Timer(timer) => {
+            if template.timer.dynamic_timer.unwrap_or_default() != timer {
+                template.timer.dynamic_timer = Some(timer);
+            }
+        }
+        AttackEdit::Trigger(r#type) => {
+            let previous = template.timer.dynamic_trigger_cleu_event.take();
+            template.timer.dynamic_trigger_cleu_event = r#type.map(|r#type| CleuEvent {
+                r#type,
+                event: previous.as_ref().map(|p| p.event).unwrap_or_default(),
+                counter: previous.as_ref().map(|p| p.counter).unwrap_or(1),
+            });
+        }
+        AttackEdit::TriggerEvent(event) => {
+            if let Some(trigger) = template.timer.dynamic_trigger_cleu_event.as_mut() {
+                trigger.event = event;
+            }
+        }
+        AttackEdit::TriggerCounter(counter) => {
+            if let Some(trigger) = template.timer.dynamic_trigger_cleu_event.as_mut() {
+                trigger.counter = counter.max(1);
+            }
+        }
+        AttackEdit::Type(r#type) => template.r#type = r#type,
+        AttackEdit::Power(power) => template.power = power,
+    }
+    template
+}
+
+#[component]
+pub fn CustomAttackRow(uuid: AttackUuid) -> impl IntoView {
+    let planner = use_planner();
+    let template = Memo::new(move |_| {
+        planner
+            .read()
+            .custom_attacks()
+            .get(&uuid)
+            .map(CustomAttackTemplate::from)
+    });
+    if template.get_untracked().is_none() {
+        return None;
+    }
+
+    let edit = move |edit: AttackEdit| {
+        let Some(current) = template.get_untracked() else {
+            return;
+        };
+        let edited = apply_edit(&current, edit);
+        if edited != current {
+            planner.update(|planner| planner.update_custom_attack(uuid, edited));
+        }
+    };
+
+    // The timer input keeps its own text, so it is only rebuilt when the stored timer changes
+    // from outside of it (undo, redo, loading a plan) and not while the user is typing.
+    let stored_timer = move || {
+        template.with(|t| Duration::from(t.as_ref().and_then(|t| t.timer.dynamic_timer).unwrap_or_default()))
+    };
+    let timer = RwSignal::new(untrack(stored_timer));
+    let timer_input = Memo::new(move |previous: Option<&(Duration, usize)>| {
+        let stored = stored_timer();
+        match previous {
+            Some(previous) if stored == timer.get_untracked() => *previous,
+            Some((_, version)) => (stored, version + 1),
+            None => (stored, 0),
+        }
+    });
+    Effect::new(move |_| edit(AttackEdit::Timer(timer.get().into())));
+    let trigger = Memo::new(move |_| {
+        template.with(|t| t.as_ref().and_then(|t| t.timer.dynamic_trigger_cleu_event.clone()))
+    });
+
+    let input_class = "rounded-md border border-slate-900 bg-slate-700 px-2 py-0.5 text-slate-300 \
+        focus-visible:bg-slate-900 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-slate-500";
+
+    Some(view! {
+        <div class="flex flex-wrap items-end gap-2">
+            <fieldset>
+                <label class="block text-sm">"Name"</label>
+                <input
+                    type="text"
+                    class=input_class
+                    prop:value=move || template.read().as_ref().map(|t| t.name.clone()).unwrap_or_default()
+                    on:change=move |ev| edit(AttackEdit::Name(event_target_value(&ev)))
+                />
+            </fieldset>
+            {move || {
+                let (initial_value, _) = timer_input.get();
+                view! { <TimerInput label="Time" initial_value=initial_value set_value=timer.write_only() /> }
+            }}
+            <fieldset>
+                <label class="block text-sm">"Trigger"</label>
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        edit(AttackEdit::Trigger(TRIGGER_TYPES.into_iter().find(|t| t.to_string() == value)));
+                    }
+                >
+                    <option
+                        value=""
+                        selected=move || trigger.read().is_none()
+                    >
+                        "Pull"
+                    </option>
+                    {TRIGGER_TYPES
+                        .into_iter()
+                        .map(|r#type| {
+                            let label = r#type.to_string();
+                            let selected = {
+                                let r#type = r#type.clone();
+                                move || trigger.read().as_ref().is_some_and(|event| event.r#type == r#type)
+                            };
+                            view! {
+                                <option value=label.clone() selected=selected>
+                                    {label}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </fieldset>
+            <Show when=move || trigger.read().is_some()>
+                <fieldset>
+                    <label class="block text-sm">"Spell ID"</label>
+                    <input
+                        type="number"
+                        min="0"
+                        class=format!("{input_class} w-24")
+                        prop:value=move || {
+                            trigger.read().as_ref().map(|event| event.event.to_string()).unwrap_or_default()
+                        }
+                        on:change=move |ev| {
+                            if let Ok(event) = u64::from_str(&event_target_value(&ev)) {
+                                edit(AttackEdit::TriggerEvent(event));
+                            }
+                        }
+                    />
+                </fieldset>
+                <fieldset>
+                    <label class="block text-sm">"Count"</label>
+                    <input
+                        type="number"
+                        min="1"
+                        class=format!("{input_class} w-16")
+                        prop:value=move || {
+                            trigger.read().as_ref().map(|event| event.counter.to_string()).unwrap_or_default()
+                        }
+                        on:change=move |ev| {
+                            if let Ok(counter) = u64::from_str(&event_target_value(&ev)) {
+                                edit(AttackEdit::TriggerCounter(counter));
+                            }
+                        }
+                    />
+                </fieldset>
+            </Show>
+            <fieldset>
+                <label class="block text-sm">"Type"</label>
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        if let Some((r#type, _)) = ATTACK_TYPES.into_iter().find(|(_, label)| *label == value) {
+                            edit(AttackEdit::Type(r#type));
+                        }
+                    }
+                >
+                    {ATTACK_TYPES
+                        .into_iter()
+                        .map(|(r#type, label)| {
+                            view! {
+                                <option value=label selected=move || {
+                                    template.read().as_ref().is_some_and(|t| t.r#type == r#type)
+                                }>
+                                    {label}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </fieldset>
+            <fieldset>
+                <label class="block text-sm">"Power"</label>
+                <input
+                    type="number"
+                    min="0"
+                    step="0.1"
+                    class=format!("{input_class} w-20")
+                    prop:value=move || template.read().as_ref().map(|t| t.power.to_string()).unwrap_or_default()
+                    on:change=move |ev| {
+                        if let Some(power) = f64::from_str(&event_target_value(&ev))
+                            .ok()
+                            .and_then(|power| NotNan::new(power).ok())
+                            .filter(|power| power.into_inner() >= 0.0)
+                        {
+                            edit(AttackEdit::Power(power));
+                        }
+                    }
+                />
+            </fieldset>
+            <button
+                class="h-7 w-7 rounded-md border border-slate-900 bg-slate-500 text-slate-900 \
+                hover:border-slate-600 hover:bg-red-400"
+                on:mousedown=move |ev| {
+                    if ev.button() != 0 {
+                        return;
+                    }
+                    planner.update(|planner| planner.remove_custom_attack(uuid));
+                }
+            >
+                <div class="fas fa-trash"></div>
+            </button>
+        </div>
+    })
+}
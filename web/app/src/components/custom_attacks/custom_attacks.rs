@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+use num_traits::Zero;
+use ordered_float::NotNan;
+
+use fight_domain::{AttackTimer, AttackType, TimeStep};
+use planner::CustomAttackTemplate;
+
+use crate::components::custom_attacks::custom_attack_row::CustomAttackRow;
+use crate::context::use_planner;
+
+#[component]
+pub fn CustomAttacks() -> impl IntoView {
+    let planner = use_planner();
+
+    view! {
+        <div class="relative z-10 flex h-full w-full flex-col gap-2 overflow-y-auto p-2">
+            <For
+                each=move || {
+                    planner.read().custom_attacks().iter().map(|a| a.uuid).collect::<Vec<_>>()
+                }
+                key=|uuid| *uuid
+                children=move |uuid| view! { <CustomAttackRow uuid /> }
+            />
+            <div>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        planner.update(|planner| {
+                            planner.add_custom_attack(CustomAttackTemplate {
+                                name: "Custom".to_string(),
+                                identifier: None,
+                                power: NotNan::zero(),
+                                r#type: AttackType::Generic,
+                                timer: AttackTimer {
+                                    dynamic_timer: Some(TimeStep::zero()),
+                                    ..Default::default()
+                                },
+                            });
+                        })
+                    }
+                >
+                    <span class="fas fa-plus mr-1"></span>
+                    "Add custom attack"
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,2 @@
+pub mod custom_attack_row;
+pub mod custom_attacks;
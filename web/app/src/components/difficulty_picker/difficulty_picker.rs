@@ -0,0 +1,39 @@
+use leptos::prelude::*;
+
+use crate::context::use_planner;
+
+#[component]
+pub fn DifficultyPicker() -> impl IntoView {
+    let planner = use_planner();
+    let selected_difficulty = Memo::new(move |_| planner.read().selected_difficulty());
+    let difficulties = Memo::new(move |_| planner.read().difficulties());
+
+    view! {
+        <div class="absolute right-2 top-2 z-10 flex space-x-2">
+            {move || {
+                difficulties
+                    .get()
+                    .into_iter()
+                    .map(|difficulty| {
+                        let selected = Signal::derive(move || selected_difficulty.get() == difficulty);
+                        view! {
+                            <button
+                                class="font-title text-lg hover:text-white"
+                                class=("text-slate-400", move || !selected.get())
+                                class=("underline", selected)
+                                on:mousedown=move |ev| {
+                                    if ev.button() != 0 {
+                                        return;
+                                    }
+                                    planner.update(|planner| planner.set_selected_difficulty(difficulty));
+                                }
+                            >
+                                {difficulty.to_string()}
+                            </button>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
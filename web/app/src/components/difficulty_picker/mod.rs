@@ -0,0 +1 @@
+pub mod difficulty_picker;
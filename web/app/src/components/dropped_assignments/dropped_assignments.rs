@@ -0,0 +1,51 @@
+use leptos::prelude::*;
+
+use crate::context::{use_dropped_assignments, use_planner};
+
+/// Notice listing the assignments that could not be kept after a class, spec or character change.
+#[component]
+pub fn DroppedAssignments() -> impl IntoView {
+    let planner = use_planner();
+    let dropped = use_dropped_assignments();
+
+    let dismiss = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        dropped.set(vec![]);
+    };
+
+    move || {
+        let dropped_assignments = dropped.get();
+        if dropped_assignments.is_empty() {
+            return None;
+        }
+        let rows = planner.with_untracked(|planner| {
+            let locale = planner.locale();
+            dropped_assignments
+                .iter()
+                .map(|dropped| {
+                    let attack = planner
+                        .attacks()
+                        .iter()
+                        .find(|attack| attack.uuid == dropped.attack)
+                        .map(|attack| attack.name.get(locale).to_owned())
+                        .unwrap_or_default();
+                    format!("{} on {attack}", dropped.spell_name.get(locale))
+                })
+                .collect::<Vec<_>>()
+        });
+        Some(view! {
+            <div class="fixed bottom-2 right-2 z-50 flex flex-col gap-1 rounded-md border-2 \
+                border-slate-900 bg-slate-800 p-2 text-sm">
+                <div class="flex items-center gap-2">
+                    <span class="font-bold">"Assignments without a matching spell were removed"</span>
+                    <button class="fas fa-xmark hover:text-white" on:mousedown=dismiss></button>
+                </div>
+                <ul class="text-red-300">
+                    {rows.into_iter().map(|row| view! { <li>{row}</li> }).collect_view()}
+                </ul>
+            </div>
+        })
+    }
+}
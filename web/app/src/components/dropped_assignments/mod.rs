@@ -0,0 +1 @@
+pub mod dropped_assignments;
\ No newline at end of file
@@ -0,0 +1,183 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::api::{download_file, write_to_clipboard};
+use crate::context::use_planner;
+use crate::serverfns::{create_plan, my_plans, plan_error_message, update_plan};
+
+const XLSX_MIME_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+#[component]
+pub fn Export() -> impl IntoView {
+    let planner = use_planner();
+    let personal = RwSignal::new(false);
+    let messages = Memo::new(move |_| {
+        let planner = planner.read();
+        let messages = if personal.get() {
+            planner.export_discord_personal()
+        } else {
+            planner.export_discord()
+        };
+        messages.unwrap_or_default()
+    });
+
+    let copy_share_link = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let Ok(origin) = web_sys::window().unwrap().location().origin() else {
+            return;
+        };
+        let link = format!("{origin}/shared#{}", planner.read().share_code());
+        write_to_clipboard(link, || error!("Failed to copy the share link"));
+    };
+    // the plan saved on the server in this session, saving again updates it
+    let server_plan_id = RwSignal::new(None::<String>);
+    let save_error = RwSignal::new(None::<String>);
+    let online_plans = Resource::new(move || server_plan_id.get(), |_| my_plans());
+    let save_online = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let plan = planner.read().saved_plan();
+        spawn_local(async move {
+            let result = match server_plan_id.get_untracked() {
+                Some(id) => update_plan(id.clone(), plan).await.map(|_| id),
+                None => create_plan(plan).await,
+            };
+            match result {
+                Ok(id) => {
+                    let origin = web_sys::window().unwrap().location().origin().unwrap_or_default();
+                    write_to_clipboard(format!("{origin}/plan/{id}"), || {});
+                    server_plan_id.set(Some(id));
+                    save_error.set(None);
+                }
+                Err(err) => save_error.set(Some(plan_error_message(&err))),
+            }
+        });
+    };
+    let download_csv = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        if let Some(sheet) = planner.read().export_sheet() {
+            download_file("cooldown-plan.csv", "text/csv", sheet.to_csv().as_bytes());
+        }
+    };
+    let download_xlsx = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        match planner.read().export_sheet().map(|sheet| sheet.to_xlsx()) {
+            Some(Ok(bytes)) => download_file("cooldown-plan.xlsx", XLSX_MIME_TYPE, &bytes),
+            Some(Err(err)) => error!("Failed to export spreadsheet: {err}"),
+            None => {}
+        }
+    };
+
+    view! {
+        <div class="relative z-10 flex h-full w-full flex-col gap-2 overflow-y-auto p-2">
+            <div class="flex gap-2">
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=download_csv
+                >
+                    <span class="fas fa-file-csv mr-1"></span>
+                    "Download CSV"
+                </button>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=download_xlsx
+                >
+                    <span class="fas fa-file-excel mr-1"></span>
+                    "Download XLSX"
+                </button>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=copy_share_link
+                >
+                    <span class="fas fa-link mr-1"></span>
+                    "Copy share link"
+                </button>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    title="Saves the plan to your account and copies its link"
+                    on:mousedown=save_online
+                >
+                    <span class="fas fa-cloud-arrow-up mr-1"></span>
+                    {move || if server_plan_id.get().is_some() { "Update online plan" } else { "Save online" }}
+                </button>
+            </div>
+            {move || save_error.get().map(|error| view! { <p class="text-sm text-red-400">{error}</p> })}
+            <Suspense>
+                {move || Suspend::new(async move {
+                    // not logged in, or nothing saved yet
+                    let plans = online_plans.await.unwrap_or_default();
+                    (!plans.is_empty()).then(|| view! {
+                        <div class="flex flex-col text-sm">
+                            <span class="font-bold">"Online plans"</span>
+                            {plans
+                                .into_iter()
+                                .map(|plan| view! {
+                                    <a class="w-fit hover:text-white" href=format!("/plan/{}", plan.id)>
+                                        {format!(
+                                            "{} {} - {} characters",
+                                            plan.difficulty,
+                                            plan.fight,
+                                            plan.characters,
+                                        )}
+                                    </a>
+                                })
+                                .collect_view()}
+                        </div>
+                    })
+                })}
+            </Suspense>
+            <label class="flex items-center gap-1 text-sm">
+                <input
+                    type="checkbox"
+                    prop:checked=personal
+                    on:change=move |ev| personal.set(event_target_checked(&ev))
+                />
+                "Summary per player"
+            </label>
+            {move || messages
+                .get()
+                .into_iter()
+                .enumerate()
+                .map(|(index, message)| {
+                    let copied = message.clone();
+                    view! {
+                        <div class="flex gap-2">
+                            <pre class="flex-1 whitespace-pre-wrap rounded-md border-2 border-slate-500 bg-slate-900 px-1 text-sm text-slate-300">
+                                {message}
+                            </pre>
+                            <button
+                                class="h-8 rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                                hover:border-slate-600 hover:bg-slate-400 \
+                                focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                                on:mousedown=move |ev| {
+                                    if ev.button() != 0 {
+                                        return;
+                                    }
+                                    write_to_clipboard(copied.clone(), || {});
+                                }
+                            >
+                                <span class="fab fa-discord mr-1"></span>
+                                {format!("Copy message {}", index + 1)}
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
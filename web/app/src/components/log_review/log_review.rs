@@ -0,0 +1,356 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::HtmlInputElement;
+
+use fight_domain::{Identifier, TimeStep};
+use planner::combat_log::{
+    damage_taken, extract_timeline, parse_combat_log, parse_warcraft_logs, retime_attacks,
+    CastTiming, CombatLogEncounter,
+};
+use planner::fights::log_fight::LogFight;
+
+use crate::context::use_planner;
+
+#[component]
+pub fn LogReview() -> impl IntoView {
+    let planner = use_planner();
+    let encounters = RwSignal::new(Vec::<CombatLogEncounter>::new());
+    let selected = RwSignal::new(0usize);
+
+    // the log is parsed in the browser, nothing is uploaded
+    let read_log = move |ev: web_sys::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        spawn_local(async move {
+            let Some(text) = JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string()) else {
+                error!("Failed to read {}", file.name());
+                return;
+            };
+            selected.set(0);
+            encounters.set(parse_combat_log(&text));
+        });
+    };
+
+    // responses of the Warcraft Logs API, one file per query or page of events
+    let read_report = move |ev: web_sys::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(files) = input.files() else {
+            return;
+        };
+        let files = (0..files.length()).filter_map(|index| files.get(index)).collect::<Vec<_>>();
+        spawn_local(async move {
+            let mut responses = vec![];
+            for file in files {
+                let Some(text) = JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string()) else {
+                    error!("Failed to read {}", file.name());
+                    return;
+                };
+                responses.push(text);
+            }
+            let responses = responses.iter().map(String::as_str).collect::<Vec<_>>();
+            match parse_warcraft_logs(&responses) {
+                Ok(report) => {
+                    selected.set(0);
+                    encounters.set(report);
+                }
+                Err(err) => error!("Failed to read the Warcraft Logs report: {err}"),
+            }
+        });
+    };
+
+    let rows = Memo::new(move |_| {
+        let planner = planner.read();
+        let attacks = planner.attacks();
+        encounters.with(|encounters| {
+            let Some(encounter) = encounters.get(selected.get()) else {
+                return vec![];
+            };
+            planner
+                .verify_plan(encounter)
+                .into_iter()
+                .map(|verification| {
+                    let planned = &verification.planned;
+                    let spell = planner
+                        .characters()
+                        .get(&planned.character)
+                        .and_then(|character| character.spells.get(&planned.spell))
+                        .map(|spell| spell.name.get(planner.locale()).to_owned())
+                        .unwrap_or_default();
+                    let attack = attacks
+                        .get(&planned.attack)
+                        .map(|attack| attack.name.get(planner.locale()).to_owned())
+                        .unwrap_or_default();
+                    (
+                        planned.character_name.clone(),
+                        spell,
+                        attack,
+                        format_time(verification.expected),
+                        format_time(verification.cast),
+                        verification.timing,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let phase_spells = RwSignal::new(String::new());
+    let timeline = Memo::new(move |_| {
+        let phase_spells = phase_spells
+            .get()
+            .split([',', ' '])
+            .filter_map(|spell_id| spell_id.trim().parse().ok())
+            .collect::<Vec<u32>>();
+        encounters.with(|encounters| {
+            let Some(encounter) = encounters.get(selected.get()) else {
+                return vec![];
+            };
+            extract_timeline(encounters, encounter.encounter_id, &phase_spells)
+        })
+    });
+    let retimed = Memo::new(move |_| {
+        let planner = planner.read();
+        let attacks = planner.attacks();
+        timeline.with(|timeline| {
+            retime_attacks(&attacks, timeline)
+                .into_iter()
+                .map(|attack| {
+                    let current = attacks
+                        .get(&attack.uuid)
+                        .map(|current| current.timer.static_timer().to_string())
+                        .unwrap_or_default();
+                    (
+                        attack.name.get(planner.locale()).to_owned(),
+                        current,
+                        attack.timer.static_timer().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let damage = Memo::new(move |_| {
+        encounters.with(|encounters| {
+            let Some(encounter) = encounters.get(selected.get()) else {
+                return vec![];
+            };
+            damage_taken(encounters, encounter.encounter_id)
+        })
+    });
+    let damage_rows = Memo::new(move |_| {
+        let planner = planner.read();
+        let attacks = planner.attacks();
+        damage
+            .get()
+            .into_iter()
+            .map(|damage| {
+                // power and type of the first attack of the spell, they are the same for all of them
+                let current = attacks
+                    .iter()
+                    .find(|attack| attack.identifier == Some(Identifier::Spell(damage.spell_id)))
+                    .map(|attack| format!("{:.2} {}", attack.power, attack.r#type.color_name()))
+                    .unwrap_or_default();
+                (damage, current)
+            })
+            .collect::<Vec<_>>()
+    });
+    let apply_damage = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let damage = damage.get_untracked();
+        planner.update(|planner| planner.apply_attack_damage(&damage));
+    };
+    let create_fight = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        let Some((name, encounter_id)) = encounters.with_untracked(|encounters| {
+            let encounter = encounters.get(selected.get_untracked())?;
+            Some((encounter.name.clone(), encounter.encounter_id))
+        }) else {
+            return;
+        };
+        let timeline = timeline.get_untracked();
+        let damage = damage.get_untracked();
+        planner.update(|planner| {
+            let difficulty = planner.selected_difficulty();
+            let fight = LogFight::new(&name, encounter_id, difficulty, &timeline, &damage);
+            planner.add_fight(Arc::new(fight));
+        });
+    };
+
+    view! {
+        <div class="relative z-10 flex h-full w-full flex-col gap-2 overflow-y-auto p-2">
+            <label class="flex items-center gap-2 text-sm">
+                <span class="fas fa-scroll"></span>
+                "WoWCombatLog.txt"
+                <input type="file" accept=".txt" on:change=read_log />
+            </label>
+            <label class="flex items-center gap-2 text-sm">
+                <span class="fas fa-chart-line"></span>
+                "Warcraft Logs API responses"
+                <input type="file" accept=".json" multiple on:change=read_report />
+            </label>
+            <select
+                class="w-fit rounded-md border-2 border-slate-500 bg-slate-900 px-1 text-slate-300"
+                on:change=move |ev| selected.set(event_target_value(&ev).parse().unwrap_or_default())
+            >
+                {move || encounters
+                    .get()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, encounter)| {
+                        let result = match encounter.kill {
+                            Some(true) => "kill",
+                            Some(false) => "wipe",
+                            None => "incomplete",
+                        };
+                        let duration = TimeStep::from(encounter.duration);
+                        view! {
+                            <option value=index.to_string() selected=move || selected.get() == index>
+                                {format!("{} {duration} ({result})", encounter.name)}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+            <table class="w-fit text-left text-sm">
+                <thead>
+                    <tr>
+                        <th class="px-2">"Character"</th>
+                        <th class="px-2">"Spell"</th>
+                        <th class="px-2">"Attack"</th>
+                        <th class="px-2">"Planned"</th>
+                        <th class="px-2">"Cast"</th>
+                        <th class="px-2"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {move || rows
+                        .get()
+                        .into_iter()
+                        .map(|(character, spell, attack, expected, cast, timing)| {
+                            let (text, class) = match timing {
+                                CastTiming::OnTime => ("On time", "text-green-400"),
+                                CastTiming::Early => ("Early", "text-yellow-300"),
+                                CastTiming::Late => ("Late", "text-orange-400"),
+                                CastTiming::Missed => ("Missed", "text-red-400"),
+                                CastTiming::NotDue => ("Not reached", "text-slate-400"),
+                            };
+                            view! {
+                                <tr>
+                                    <td class="px-2">{character}</td>
+                                    <td class="px-2">{spell}</td>
+                                    <td class="px-2">{attack}</td>
+                                    <td class="px-2">{expected}</td>
+                                    <td class="px-2">{cast}</td>
+                                    <td class=format!("px-2 {class}")>{text}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+            <h2 class="font-bold">"Damage taken in the pulls"</h2>
+            <button
+                class="w-fit rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                hover:border-slate-600 hover:bg-slate-400 \
+                focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                on:mousedown=apply_damage
+            >
+                <span class="fas fa-bolt mr-1"></span>
+                "Apply to custom attacks"
+            </button>
+            <table class="w-fit text-left text-sm">
+                <thead>
+                    <tr>
+                        <th class="px-2">"Attack"</th>
+                        <th class="px-2">"Hits"</th>
+                        <th class="px-2">"Damage per hit"</th>
+                        <th class="px-2">"Players hit"</th>
+                        <th class="px-2">"Suggested"</th>
+                        <th class="px-2">"Current"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {move || damage_rows
+                        .get()
+                        .into_iter()
+                        .map(|(damage, current)| view! {
+                            <tr>
+                                <td class="px-2">{damage.name}</td>
+                                <td class="px-2">{damage.occurrences}</td>
+                                <td class="px-2">{damage.damage}</td>
+                                <td class="px-2">{format!("{:.1}", damage.targets)}</td>
+                                <td class="px-2">
+                                    {format!("{:.2} {}", damage.power, damage.r#type.color_name())}
+                                </td>
+                                <td class="px-2">{current}</td>
+                            </tr>
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+            <h2 class="font-bold">"Boss timeline of the pulls"</h2>
+            <div class="flex gap-2">
+                <input
+                    class="rounded-md border-2 border-slate-500 bg-slate-900 px-1 text-slate-300 focus-visible:outline-none"
+                    placeholder="Phase change spell ids"
+                    prop:value=phase_spells
+                    on:input=move |ev| phase_spells.set(event_target_value(&ev))
+                />
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=create_fight
+                >
+                    <span class="fas fa-timeline mr-1"></span>
+                    "Create fight from timeline"
+                </button>
+            </div>
+            <table class="w-fit text-left text-sm">
+                <thead>
+                    <tr>
+                        <th class="px-2">"Attack"</th>
+                        <th class="px-2">"Spell"</th>
+                        <th class="px-2">"Timer"</th>
+                        <th class="px-2">"Pulls"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {move || timeline
+                        .get()
+                        .into_iter()
+                        .map(|attack| view! {
+                            <tr>
+                                <td class="px-2">{attack.name}</td>
+                                <td class="px-2">{attack.spell_id}</td>
+                                <td class="px-2">{attack.timer.static_timer().to_string()}</td>
+                                <td class="px-2">{attack.pulls}</td>
+                            </tr>
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+            <h2 class="font-bold">"Timers of the selected fight according to the pulls"</h2>
+            <ul class="text-sm">
+                {move || retimed
+                    .get()
+                    .into_iter()
+                    .map(|(name, current, retimed)| view! {
+                        <li>{format!("{name}: {current} -> {retimed}")}</li>
+                    })
+                    .collect_view()}
+            </ul>
+        </div>
+    }
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map(|time| TimeStep::from(time).to_string()).unwrap_or_default()
+}
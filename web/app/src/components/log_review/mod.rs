@@ -0,0 +1 @@
+pub mod log_review;
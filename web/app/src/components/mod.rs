@@ -2,13 +2,23 @@ pub mod add_character_button;
 pub mod attacks;
 pub mod character_headers;
 pub mod corner_buttons;
+pub mod custom_attacks;
+pub mod difficulty_picker;
+pub mod dropped_assignments;
 pub mod error;
+pub mod export;
 pub mod icons;
 pub mod inputs;
+pub mod log_review;
 pub mod login;
 pub mod main;
+pub mod note_import;
+pub mod plan_page;
+pub mod recent_plans;
+pub mod shared_plan;
 pub mod character_spell_toggles;
 pub mod assignment_icons;
 pub mod nav;
+pub mod spell_editor;
 pub mod autocomplete;
 pub mod character_backgrounds;
\ No newline at end of file
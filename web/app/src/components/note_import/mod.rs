@@ -0,0 +1 @@
+pub mod note_import;
\ No newline at end of file
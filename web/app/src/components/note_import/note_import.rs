@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use js_sys::Uint8Array;
+use leptos::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::HtmlInputElement;
+
+use planner::fights::note_fight::NoteFight;
+use planner::spreadsheet::{Sheet, SheetImportReport};
+use planner::NoteImportReport;
+
+use crate::context::use_planner;
+
+#[component]
+pub fn NoteImport() -> impl IntoView {
+    let planner = use_planner();
+    let note = RwSignal::new(String::new());
+    let report = RwSignal::new(None::<NoteImportReport>);
+    let fight_name = RwSignal::new(String::new());
+    let sheet_report = RwSignal::new(None::<SheetImportReport>);
+
+    let import_sheet = move |ev: web_sys::Event| {
+        let input = event_target::<HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        spawn_local(async move {
+            let Ok(buffer) = JsFuture::from(file.array_buffer()).await else {
+                error!("Failed to read {}", file.name());
+                return;
+            };
+            let bytes = Uint8Array::new(&buffer).to_vec();
+            let sheet = if file.name().ends_with(".xlsx") {
+                match Sheet::from_xlsx(&bytes) {
+                    Ok(sheet) => sheet,
+                    Err(err) => {
+                        error!("Failed to read spreadsheet: {err}");
+                        return;
+                    }
+                }
+            } else {
+                Sheet::from_csv(&String::from_utf8_lossy(&bytes))
+            };
+            sheet_report.set(planner.try_update(|planner| planner.import_sheet(&sheet)));
+        });
+    };
+
+    view! {
+        <div class="relative z-10 flex h-full w-full flex-col gap-2 overflow-y-auto p-2">
+            <textarea
+                class="h-40 w-full rounded-md border-2 border-slate-500 bg-slate-900 px-1 font-mono text-sm text-slate-300 focus-visible:outline-none"
+                placeholder="Paste an MRT note"
+                prop:value=note
+                on:input=move |ev| note.set(event_target_value(&ev))
+            />
+            <div class="flex gap-2">
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        let note = note.get_untracked();
+                        report.set(planner.try_update(|planner| planner.import_note(&note)));
+                    }
+                >
+                    <span class="fas fa-file-import mr-1"></span>
+                    "Import note"
+                </button>
+                <input
+                    class="rounded-md border-2 border-slate-500 bg-slate-900 px-1 text-slate-300 focus-visible:outline-none"
+                    placeholder="Fight name"
+                    prop:value=fight_name
+                    on:input=move |ev| fight_name.set(event_target_value(&ev))
+                />
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        let note = note.get_untracked();
+                        let name = fight_name.get_untracked();
+                        let name = if name.trim().is_empty() { "Custom fight" } else { name.trim() };
+                        planner.update(|planner| {
+                            let fight = NoteFight::new(name, planner.selected_difficulty(), &note);
+                            planner.add_fight(Arc::new(fight));
+                        });
+                    }
+                >
+                    <span class="fas fa-timeline mr-1"></span>
+                    "Create fight from note"
+                </button>
+            </div>
+            <label class="flex items-center gap-2 text-sm">
+                <span class="fas fa-file-excel"></span>
+                "Import spreadsheet (CSV/XLSX)"
+                <input type="file" accept=".csv,.xlsx" on:change=import_sheet />
+            </label>
+            {move || report.get().map(|report| view! {
+                <div class="text-sm">
+                    <p>{format!("Imported {} assignments", report.imported)}</p>
+                    <ul class="text-red-300">
+                        {report
+                            .problems
+                            .iter()
+                            .map(|problem| view! { <li>{problem.to_string()}</li> })
+                            .collect_view()}
+                    </ul>
+                </div>
+            })}
+            {move || sheet_report.get().map(|report| view! {
+                <div class="text-sm">
+                    <p>{format!("Imported {} assignments from the spreadsheet", report.imported)}</p>
+                    <ul class="text-red-300">
+                        {report
+                            .problems
+                            .iter()
+                            .map(|problem| view! { <li>{problem.to_string()}</li> })
+                            .collect_view()}
+                    </ul>
+                </div>
+            })}
+        </div>
+    }
+}
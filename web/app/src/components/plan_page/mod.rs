@@ -0,0 +1 @@
+pub mod plan_page;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use planner::PlannerState;
+
+use crate::components::shared_plan::shared_plan::ReadOnlyPlanner;
+use crate::context::aberrus_fights;
+use crate::serverfns::{aberrus, plan_error_message, server_plan};
+
+/// Read-only view of a plan saved on the server, rendered on the server for link previews.
+#[component]
+pub fn PlanPage() -> impl IntoView {
+    let params = use_params_map();
+    let plan = Resource::new(
+        move || params.read().get("id").unwrap_or_default(),
+        |id| server_plan(id),
+    );
+    let aberrus = Resource::new(|| (), |_| aberrus());
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                match plan.await {
+                    Ok(plan) => {
+                        // the plan finds its fight by the journal data, so the fights need it first
+                        let aberrus = aberrus.await.ok();
+                        let mut planner = PlannerState::new(aberrus_fights(aberrus.as_ref()));
+                        planner.load_saved_plan(&plan);
+                        view! { <ReadOnlyPlanner planner/> }.into_any()
+                    }
+                    Err(e) => view! { <p class="p-4 text-red-400">{plan_error_message(&e)}</p> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
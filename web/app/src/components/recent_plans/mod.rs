@@ -0,0 +1 @@
+pub mod recent_plans;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+
+use crate::context::{load_plan, use_planner, use_recent_plans};
+
+#[component]
+pub fn RecentPlans() -> impl IntoView {
+    let planner = use_planner();
+    let recent = use_recent_plans();
+
+    view! {
+        <div class="relative z-10 flex flex-col gap-1 p-2 text-sm">
+            <span class="font-bold">"Recent plans"</span>
+            {move || recent
+                .get()
+                .into_iter()
+                .map(|recent| {
+                    let saved_at = js_sys::Date::new(&recent.saved_at.into())
+                        .to_locale_string("default", &Default::default())
+                        .as_string()
+                        .unwrap_or_default();
+                    let key = recent.key.clone();
+                    view! {
+                        <button
+                            class="w-fit text-left hover:text-white"
+                            on:mousedown=move |ev| {
+                                if ev.button() != 0 {
+                                    return;
+                                }
+                                let Some(plan) = load_plan(&key) else {
+                                    return;
+                                };
+                                planner.update(|planner| planner.load_saved_plan(&plan));
+                            }
+                        >
+                            {format!(
+                                "{} {} - {} characters - {saved_at}",
+                                recent.difficulty,
+                                recent.fight,
+                                recent.characters,
+                            )}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
@@ -0,0 +1 @@
+pub mod shared_plan;
\ No newline at end of file
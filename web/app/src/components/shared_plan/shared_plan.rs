@@ -0,0 +1,100 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use planner::PlannerState;
+
+use crate::components::assignment_icons::assignment_icons::AssignmentIcons;
+use crate::components::attacks::attacks::Attacks;
+use crate::components::character_backgrounds::character_backgrounds::CharacterBackgrounds;
+use crate::components::character_headers::character_headers::CharacterHeaders;
+use crate::components::character_spell_toggles::character_spell_toggles::CharacterSpellToggles;
+use crate::components::corner_buttons::CornerButtons;
+use crate::components::main::main::Main;
+use crate::context::{aberrus_fights, provide_read_only, save_plan, use_planner};
+use crate::serverfns::aberrus;
+
+/// Read-only view of a plan from a share link, the plan is in the URL fragment so it never
+/// reaches the server.
+#[component]
+pub fn SharedPlan() -> impl IntoView {
+    let shared = RwSignal::new(None::<PlannerState>);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let Ok(hash) = web_sys::window().unwrap().location().hash() else {
+            return;
+        };
+        spawn_local(async move {
+            // the plan finds its fight by the journal data, so the fights need it before loading
+            let aberrus = aberrus()
+                .await
+                .inspect_err(|e| {
+                    web_sys::console::log_1(&format!("failed to load fight data: {e}").into())
+                })
+                .ok();
+            let mut planner = PlannerState::new(aberrus_fights(aberrus.as_ref()));
+            match planner.load_share_code(&hash) {
+                Ok(()) => shared.set(Some(planner)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    view! {
+        {move || error.get().map(|error| view! { <p class="p-4 text-red-400">{error}</p> })}
+        {move || shared.get().map(|planner| view! { <ReadOnlyPlanner planner/> })}
+    }
+}
+
+/// The planner showing a plan without editing it, with a button to continue with a copy of it in
+/// the planner.
+#[component]
+pub fn ReadOnlyPlanner(planner: PlannerState) -> impl IntoView {
+    let plan = planner.saved_plan();
+    provide_read_only(planner);
+    let planner = use_planner();
+    let tab_open = RwSignal::new(false);
+
+    let make_copy = move |ev: web_sys::MouseEvent| {
+        if ev.button() != 0 {
+            return;
+        }
+        save_plan(&plan);
+        let _ = web_sys::window().unwrap().location().set_href("/");
+    };
+    let title = move || {
+        let planner = planner.read();
+        let encounter = planner
+            .selected_fight()
+            .and_then(|fight| fight.data().clone())
+            .map(|data| data.encounter_name.get(planner.locale()).to_owned())
+            .unwrap_or_default();
+        format!("{} {encounter}", planner.selected_difficulty())
+    };
+
+    view! {
+        <div class="flex h-full min-h-screen w-full select-none flex-col">
+            <header class="flex items-center gap-2 p-2 text-slate-100">
+                <span class="font-bold">{title}</span>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400 \
+                    focus-visible:outline focus-visible:outline-offset-2 focus-visible:outline-slate-300"
+                    title="Replaces your plan for this fight"
+                    on:mousedown=make_copy
+                >
+                    <span class="fas fa-copy mr-1"></span>
+                    "Make a copy"
+                </button>
+            </header>
+            <Main tab_open>
+                <CharacterBackgrounds tab_open />
+                <CornerButtons/>
+                <Attacks/>
+                <AssignmentIcons/>
+                <CharacterSpellToggles/>
+                <CharacterHeaders/>
+            </Main>
+        </div>
+    }
+}
@@ -0,0 +1,2 @@
+pub mod spell_editor;
+pub mod spell_editor_row;
@@ -0,0 +1,124 @@
+use leptos::prelude::*;
+use num_traits::One;
+use ordered_float::NotNan;
+
+use fight_domain::{CharacterUuid, Identifier, TimeStep};
+use i18n::Locale;
+use planner::CustomSpellTemplate;
+
+use crate::components::spell_editor::spell_editor_row::SpellEditorRow;
+use crate::context::use_planner;
+
+#[component]
+pub fn SpellEditor() -> impl IntoView {
+    let planner = use_planner();
+    let selected_character = RwSignal::new(None::<CharacterUuid>);
+
+    let characters = Memo::new(move |_| {
+        planner
+            .read()
+            .characters()
+            .iter()
+            .filter_map(|c| {
+                c.name
+                    .as_ref()
+                    .map(|name| (c.uuid, name.get(Locale::EnglishUnitedStates).to_owned()))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // fall back to the first character when nothing (or a removed character) is selected
+    let character_uuid = Memo::new(move |_| {
+        let characters = characters.get();
+        selected_character
+            .get()
+            .filter(|uuid| characters.iter().any(|(c, _)| c == uuid))
+            .or_else(|| characters.first().map(|(uuid, _)| *uuid))
+    });
+
+    view! {
+        <div class="relative z-10 flex h-full w-full flex-col gap-2 overflow-y-auto p-2">
+            <div class="flex items-end gap-2">
+                <select
+                    class="rounded-md border border-slate-900 bg-slate-700 px-2 py-0.5 text-slate-300"
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        selected_character
+                            .set(
+                                characters
+                                    .get_untracked()
+                                    .into_iter()
+                                    .find(|(uuid, _)| uuid.to_string() == value)
+                                    .map(|(uuid, _)| uuid),
+                            );
+                    }
+                >
+                    <For
+                        each=move || characters.get()
+                        key=|(uuid, name)| (*uuid, name.clone())
+                        children=move |(uuid, name)| {
+                            view! {
+                                <option
+                                    value=uuid.to_string()
+                                    selected=move || character_uuid.get() == Some(uuid)
+                                >
+                                    {name}
+                                </option>
+                            }
+                        }
+                    />
+                </select>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        let Some(character_uuid) = character_uuid.get_untracked() else {
+                            return;
+                        };
+                        planner.update(|planner| {
+                            planner.add_custom_spell(character_uuid, CustomSpellTemplate {
+                                name: "Custom".to_string(),
+                                icon_text: None,
+                                identifier: Identifier::Text("Custom".to_string()),
+                                power: NotNan::one(),
+                                cooldown: TimeStep::mm_ss(2, 0),
+                                cast_time: TimeStep::mm_ss(0, 1),
+                                charges: 1,
+                                exclusive_with: Default::default(),
+                                minor: false,
+                            });
+                        });
+                    }
+                >
+                    <span class="fas fa-plus mr-1"></span>
+                    "Add custom spell"
+                </button>
+            </div>
+            {move || {
+                character_uuid
+                    .get()
+                    .map(|character_uuid| {
+                        view! {
+                            <For
+                                each=move || {
+                                    planner
+                                        .read()
+                                        .characters()
+                                        .get(&character_uuid)
+                                        .map(|c| c.spells().iter().map(|s| s.uuid).collect::<Vec<_>>())
+                                        .unwrap_or_default()
+                                }
+                                key=|spell_uuid| *spell_uuid
+                                children=move |spell_uuid| {
+                                    view! { <SpellEditorRow character_uuid spell_uuid /> }
+                                }
+                            />
+                        }
+                    })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use ordered_float::NotNan;
+
+use fight_domain::{CharacterUuid, Identifier, SpellOverride, SpellUuid, TimeStep};
+use planner::{CustomSpellTemplate, PlannerState};
+
+use crate::components::inputs::timer_input::TimerInput;
+use crate::context::use_planner;
+
+#[derive(Debug, Clone, PartialEq)]
+enum SpellEdit {
+    Name(String),
+    Identifier(Identifier),
+    Cooldown(TimeStep),
+    CastTime(TimeStep),
+    Charges(usize),
+    Power(NotNan<f64>),
+    ExclusiveWith(BTreeSet<Identifier>),
+}
+
+/// Custom spells are updated directly, built-in spells get an override of the edited field.
+fn apply_edit(
+    planner: &mut PlannerState,
+    character_uuid: CharacterUuid,
+    spell_uuid: SpellUuid,
+    edit: SpellEdit,
+) {
+    let Some(character) = planner.characters().get(&character_uuid) else {
+        return;
+    };
+    let Some(spell) = character.spells().get(&spell_uuid) else {
+        return;
+    };
+    if character.is_custom_spell(spell_uuid) {
+        let mut template = CustomSpellTemplate::from(spell);
+        match edit {
+            SpellEdit::Name(name) => template.name = name,
+            SpellEdit::Identifier(identifier) => template.identifier = identifier,
+            SpellEdit::Cooldown(cooldown) => template.cooldown = cooldown,
+            SpellEdit::CastTime(cast_time) => template.cast_time = cast_time,
+            SpellEdit::Charges(charges) => template.charges = charges,
+            SpellEdit::Power(power) => template.power = power,
+            SpellEdit::ExclusiveWith(exclusive_with) => template.exclusive_with = exclusive_with,
+        }
+        if template != CustomSpellTemplate::from(spell) {
+            planner.update_custom_spell(character_uuid, spell_uuid, template);
+        }
+    } else {
+        let mut spell_override = character
+            .spell_overrides
+            .get(&spell_uuid)
+            .cloned()
+            .unwrap_or_default();
+        match edit {
+            SpellEdit::Name(name) => spell_override.name = Some(name),
+            SpellEdit::Identifier(identifier) => spell_override.identifier = Some(identifier),
+            SpellEdit::Cooldown(cooldown) => spell_override.cooldown = Some(cooldown),
+            SpellEdit::CastTime(cast_time) => spell_override.cast_time = Some(cast_time),
+            SpellEdit::Charges(charges) => spell_override.charges = Some(charges),
+            SpellEdit::Power(power) => spell_override.power = Some(power),
+            SpellEdit::ExclusiveWith(exclusive_with) => {
+                spell_override.exclusive_with = Some(exclusive_with)
+            }
+        }
+        if spell_override.apply(spell) != *spell {
+            planner.override_spell(character_uuid, spell_uuid, spell_override);
+        }
+    }
+}
+
+fn parse_spell_ids(text: &str) -> Option<BTreeSet<Identifier>> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| u32::from_str(id).ok().map(Identifier::Spell))
+        .collect()
+}
+
+#[component]
+pub fn SpellEditorRow(character_uuid: CharacterUuid, spell_uuid: SpellUuid) -> impl IntoView {
+    let planner = use_planner();
+    let spell = Memo::new(move |_| {
+        planner
+            .read()
+            .characters()
+            .get(&character_uuid)
+            .and_then(|c| c.spells().get(&spell_uuid).cloned())
+    });
+    let is_custom = Memo::new(move |_| {
+        planner
+            .read()
+            .characters()
+            .get(&character_uuid)
+            .is_some_and(|c| c.is_custom_spell(spell_uuid))
+    });
+    let is_overridden = Memo::new(move |_| {
+        planner
+            .read()
+            .characters()
+            .get(&character_uuid)
+            .is_some_and(|c| c.spell_overrides.contains_key(&spell_uuid))
+    });
+    let Some(initial) = spell.get_untracked() else {
+        return None;
+    };
+
+    let edit = move |edit: SpellEdit| {
+        planner.update(|planner| apply_edit(planner, character_uuid, spell_uuid, edit));
+    };
+
+    let cooldown = RwSignal::new(Duration::from(initial.cooldown));
+    let cast_time = RwSignal::new(Duration::from(initial.cast_time));
+    Effect::new(move |_| edit(SpellEdit::Cooldown(cooldown.get().into())));
+    Effect::new(move |_| edit(SpellEdit::CastTime(cast_time.get().into())));
+
+    let input_class = "rounded-md border border-slate-900 bg-slate-700 px-2 py-0.5 text-slate-300 \
+        focus-visible:bg-slate-900 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-slate-500";
+
+    Some(view! {
+        <div class="flex flex-wrap items-end gap-2">
+            <fieldset>
+                <label class="block text-sm">"Name"</label>
+                <input
+                    type="text"
+                    class=input_class
+                    prop:value=move || {
+                        spell.get().map(|s| s.name.get(planner.read().locale()).to_owned()).unwrap_or_default()
+                    }
+                    on:change=move |ev| edit(SpellEdit::Name(event_target_value(&ev)))
+                />
+            </fieldset>
+            <fieldset>
+                <label class="block text-sm">"Spell ID"</label>
+                <input
+                    type="text"
+                    class=format!("{input_class} w-24")
+                    prop:value=move || {
+                        spell
+                            .get()
+                            .map(|s| match s.identifier {
+                                Identifier::Spell(id) => id.to_string(),
+                                Identifier::Icon(name, _) => name,
+                                Identifier::Marker(marker) => format!("{marker:?}"),
+                                Identifier::Text(text) => text,
+                            })
+                            .unwrap_or_default()
+                    }
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        let identifier = match u32::from_str(value.trim()) {
+                            Ok(id) => Identifier::Spell(id),
+                            Err(_) => Identifier::Text(value),
+                        };
+                        edit(SpellEdit::Identifier(identifier));
+                    }
+                />
+            </fieldset>
+            <TimerInput
+                label="Cooldown"
+                initial_value=cooldown.get_untracked()
+                set_value=cooldown.write_only()
+            />
+            <TimerInput
+                label="Cast Time"
+                initial_value=cast_time.get_untracked()
+                set_value=cast_time.write_only()
+            />
+            <fieldset>
+                <label class="block text-sm">"Charges"</label>
+                <input
+                    type="number"
+                    min="1"
+                    class=format!("{input_class} w-16")
+                    prop:value=move || spell.get().map(|s| s.charges.to_string()).unwrap_or_default()
+                    on:change=move |ev| {
+                        if let Ok(charges) = usize::from_str(&event_target_value(&ev)) {
+                            edit(SpellEdit::Charges(charges.max(1)));
+                        }
+                    }
+                />
+            </fieldset>
+            <fieldset>
+                <label class="block text-sm">"Power"</label>
+                <input
+                    type="number"
+                    min="0"
+                    step="0.1"
+                    class=format!("{input_class} w-20")
+                    prop:value=move || spell.get().map(|s| s.power.to_string()).unwrap_or_default()
+                    on:change=move |ev| {
+                        if let Some(power) = f64::from_str(&event_target_value(&ev))
+                            .ok()
+                            .and_then(|power| NotNan::new(power).ok())
+                            .filter(|power| power.into_inner() >= 0.0)
+                        {
+                            edit(SpellEdit::Power(power));
+                        }
+                    }
+                />
+            </fieldset>
+            <fieldset>
+                <label class="block text-sm">"Exclusive With"</label>
+                <input
+                    type="text"
+                    placeholder="Spell IDs"
+                    class=format!("{input_class} w-32")
+                    prop:value=move || {
+                        spell
+                            .get()
+                            .map(|s| {
+                                s.exclusive_with
+                                    .iter()
+                                    .filter_map(|identifier| match identifier {
+                                        Identifier::Spell(id) => Some(id.to_string()),
+                                        _ => None,
+                                    })
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            })
+                            .unwrap_or_default()
+                    }
+                    on:change=move |ev| {
+                        if let Some(exclusive_with) = parse_spell_ids(&event_target_value(&ev)) {
+                            edit(SpellEdit::ExclusiveWith(exclusive_with));
+                        }
+                    }
+                />
+            </fieldset>
+            <Show when=move || is_overridden.get()>
+                <button
+                    class="rounded-md border border-slate-900 bg-slate-500 px-2 py-0.5 text-slate-900 \
+                    hover:border-slate-600 hover:bg-slate-400"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        planner.update(|planner| planner.reset_spell_override(character_uuid, spell_uuid));
+                    }
+                >
+                    "Reset"
+                </button>
+            </Show>
+            <Show when=move || is_custom.get()>
+                <button
+                    class="h-7 w-7 rounded-md border border-slate-900 bg-slate-500 text-slate-900 \
+                    hover:border-slate-600 hover:bg-red-400"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        planner.update(|planner| planner.remove_custom_spell(character_uuid, spell_uuid));
+                    }
+                >
+                    <div class="fas fa-trash"></div>
+                </button>
+            </Show>
+        </div>
+    })
+}
@@ -0,0 +1,15 @@
+use leptos::prelude::*;
+
+use planner::DroppedAssignment;
+
+/// Assignments lost by the last class, spec or character change, until they are dismissed.
+#[derive(Copy, Clone)]
+struct DroppedAssignments(RwSignal<Vec<DroppedAssignment>>);
+
+pub fn provide_dropped_assignments_context() {
+    provide_context(DroppedAssignments(RwSignal::new(vec![])));
+}
+
+pub fn use_dropped_assignments() -> RwSignal<Vec<DroppedAssignment>> {
+    use_context::<DroppedAssignments>().unwrap().0
+}
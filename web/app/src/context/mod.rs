@@ -1,16 +1,24 @@
 use leptos::prelude::*;
 
+use planner::PlannerState;
+
 pub use class_cache::ClassCache;
 pub use class_spec_index::{ClassSpecIndex, use_class_spec_index};
+pub use dropped_assignments::use_dropped_assignments;
 pub use guild_roster::{GuildRoster, RosterEntry};
-pub use planner_state::use_planner;
+pub use plan_storage::{load_plan, save_plan, use_recent_plans, RecentPlan};
+pub use planner_state::{aberrus_fights, use_planner};
+pub use read_only::use_read_only;
 pub use user::UserContext;
 pub use workers::with_workers;
 
 mod class_cache;
 mod class_spec_index;
+mod dropped_assignments;
 mod guild_roster;
+mod plan_storage;
 mod planner_state;
+mod read_only;
 mod user;
 mod workers;
 
@@ -19,7 +27,20 @@ pub fn provide() {
     user::provide_user_context();
     workers::provide_workers_context();
     planner_state::provide_planner_state_context();
+    plan_storage::provide_plan_storage_context();
+    dropped_assignments::provide_dropped_assignments_context();
+    crate::api::load_spell_names(use_planner(), expect_context::<UserContext>());
+    crate::api::load_fight_data(use_planner());
     class_cache::provide_class_cache_context();
     class_spec_index::provide_class_spec_index_context();
     guild_roster::provide_guild_roster_context();
 }
+
+/// Context of the planner showing a plan without editing it. The plans stored in the browser are
+/// left alone, and nothing runs the optimizer.
+pub fn provide_read_only(planner: PlannerState) {
+    user::provide_user_context();
+    planner_state::provide_planner_state(planner);
+    read_only::provide_read_only_context();
+    crate::api::load_spell_names(use_planner(), expect_context::<UserContext>());
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use web_sys::Storage;
+
+use planner::{Difficulty, SavedFight, SavedPlan};
+
+use super::use_planner;
+
+const PLAN_KEY_PREFIX: &str = "plan:";
+const RECENT_PLANS_KEY: &str = "recent-plans";
+const MAX_RECENT_PLANS: usize = 10;
+
+/// Plan saved in the browser, newest first in the recent plans.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentPlan {
+    pub key: String,
+    pub fight: String,
+    pub difficulty: Difficulty,
+    pub characters: usize,
+    /// Milliseconds since the epoch.
+    pub saved_at: f64,
+}
+
+#[derive(Copy, Clone)]
+pub struct RecentPlans(pub RwSignal<Vec<RecentPlan>>);
+
+pub fn use_recent_plans() -> RwSignal<Vec<RecentPlan>> {
+    use_context::<RecentPlans>().unwrap().0
+}
+
+/// Saves the plan whenever it changes, one plan per fight. Selecting a fight with a saved plan
+/// loads it as part of the selection.
+pub fn provide_plan_storage_context() {
+    let planner = use_planner();
+    // filled after hydration, the server has no storage to render them from
+    let recent = RwSignal::new(vec![]);
+    provide_context(RecentPlans(recent));
+
+    // effects only run in the browser once the page is hydrated, so the server render and the
+    // first client render both show the default plan
+    Effect::new(move |restored: Option<()>| {
+        if restored.is_none() {
+            planner.update(|planner| {
+                planner.set_fight_plan_loader(Arc::new(|fight| load_plan(&plan_key(fight))));
+                let last_plan = recent_plans()
+                    .first()
+                    .and_then(|recent| load_plan(&recent.key));
+                if let Some(plan) = last_plan {
+                    planner.load_saved_plan(&plan);
+                }
+            });
+            recent.set(recent_plans());
+        }
+        // fights are only known by their encounter once their journal data is loaded
+        let has_data = planner.with(|planner| {
+            planner
+                .selected_fight()
+                .is_some_and(|fight| fight.data().is_some())
+        });
+        if has_data {
+            save_plan(&planner.with(|planner| planner.saved_plan()));
+            recent.set(recent_plans());
+        }
+    });
+}
+
+pub fn load_plan(key: &str) -> Option<SavedPlan> {
+    let json = local_storage()?.get_item(key).ok()??;
+    match SavedPlan::from_json(&json) {
+        Ok(plan) => Some(plan),
+        Err(e) => {
+            error!("Failed to load {key}: {e}");
+            None
+        }
+    }
+}
+
+/// Saves the plan as the plan of its fight, replacing the one saved before.
+pub fn save_plan(plan: &SavedPlan) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    let key = plan_key(&plan.fight);
+    if storage.set_item(&key, &plan.to_json()).is_err() {
+        error!("Failed to save {key}");
+        return;
+    }
+
+    let mut recent = recent_plans();
+    recent.retain(|recent| recent.key != key);
+    recent.insert(
+        0,
+        RecentPlan {
+            key,
+            fight: plan.fight.name.clone(),
+            difficulty: plan.fight.difficulty,
+            // without the general character
+            characters: plan.characters.len().saturating_sub(1),
+            saved_at: js_sys::Date::now(),
+        },
+    );
+    for dropped in recent.drain(MAX_RECENT_PLANS.min(recent.len())..) {
+        let _ = storage.remove_item(&dropped.key);
+    }
+    let _ = storage.set_item(RECENT_PLANS_KEY, &serde_json::to_string(&recent).unwrap());
+}
+
+fn recent_plans() -> Vec<RecentPlan> {
+    local_storage()
+        .and_then(|storage| storage.get_item(RECENT_PLANS_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn plan_key(fight: &SavedFight) -> String {
+    format!(
+        "{PLAN_KEY_PREFIX}{}:{}:{}",
+        fight.encounter_id, fight.name, fight.difficulty
+    )
+}
+
+// missing when storage is disabled, the server renders the default plan
+fn local_storage() -> Option<Storage> {
+    if cfg!(feature = "ssr") {
+        return None;
+    }
+    web_sys::window()?.local_storage().ok()?
+}
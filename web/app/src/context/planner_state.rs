@@ -7,12 +7,27 @@ use leptos::prelude::guards::WriteGuard;
 use i18n::LocalizedString;
 use planner::fights::dragonflight::aberrus::kazzara::Kazzara;
 
-use planner::{PlannerCharacterTemplate, PlannerRealm, PlannerState};
+use planner::{PlannerCharacterTemplate, PlannerFight, PlannerRealm, PlannerState};
+
+use crate::serverfns::AberrusInfo;
+
+/// Fights the planner offers until their journal data is loaded.
+pub fn planner_fights() -> Vec<Arc<dyn PlannerFight>> {
+    aberrus_fights(None)
+}
+
+/// The planner fights with their journal data, which names and identifies the attacks.
+pub fn aberrus_fights(aberrus: Option<&AberrusInfo>) -> Vec<Arc<dyn PlannerFight>> {
+    let instance = aberrus.map(|aberrus| &aberrus.instance);
+    let kazzara = aberrus.map(|aberrus| &aberrus.kazzara);
+    vec![Arc::new(Kazzara::mythic(instance, kazzara))]
+}
 
 pub fn provide_planner_state_context() {
-    let planner_state = PlannerState::new(vec![
-        Arc::new(Kazzara::mythic(None, None)),
-    ]);
+    provide_planner_state(PlannerState::new(planner_fights()));
+}
+
+pub fn provide_planner_state(planner_state: PlannerState) {
     let planner_state: ArcRwSignal<PlannerState> = ArcRwSignal::new(planner_state);
     provide_context(planner_state);
 }
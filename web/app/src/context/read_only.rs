@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+
+/// Marks a planner that shows a plan without changing it, like one opened from a link.
+#[derive(Copy, Clone)]
+struct ReadOnly;
+
+pub fn provide_read_only_context() {
+    provide_context(ReadOnly);
+}
+
+pub fn use_read_only() -> bool {
+    use_context::<ReadOnly>().is_some()
+}
@@ -17,7 +17,7 @@ use leptos_meta::*;
 use leptos_router::{
     components::{Route, Router, Routes},
     params::Params,
-    StaticSegment,
+    ParamSegment, StaticSegment,
 };
 use rand::Rng;
 use wasm_bindgen::JsCast;
@@ -26,7 +26,7 @@ use i18n::{encounter, LocalizedString, preferences};
 use planner::{PlannerCharacterTemplate, PlannerFight, PlannerRealm};
 
 #[cfg(feature = "render")]
-use crate::api::use_optimizer;
+use crate::api::{use_optimizer, use_undo_shortcuts};
 #[cfg(feature = "render")]
 use crate::components::add_character_button::AddCharacterButton;
 #[cfg(feature = "render")]
@@ -42,12 +42,32 @@ use crate::components::character_spell_toggles::character_spell_toggles::Charact
 #[cfg(feature = "render")]
 use crate::components::corner_buttons::CornerButtons;
 #[cfg(feature = "render")]
+use crate::components::custom_attacks::custom_attacks::CustomAttacks;
+#[cfg(feature = "render")]
+use crate::components::difficulty_picker::difficulty_picker::DifficultyPicker;
+#[cfg(feature = "render")]
+use crate::components::dropped_assignments::dropped_assignments::DroppedAssignments;
+#[cfg(feature = "render")]
+use crate::components::export::export::Export;
+#[cfg(feature = "render")]
+use crate::components::log_review::log_review::LogReview;
+#[cfg(feature = "render")]
 use crate::components::login::logged_in::LoggedIn;
 #[cfg(feature = "render")]
 use crate::components::main::main::Main;
 #[cfg(feature = "render")]
 use crate::components::nav::{Nav, NavTabBodyBackground, Tab, TabBody, TabHeader};
 #[cfg(feature = "render")]
+use crate::components::note_import::note_import::NoteImport;
+#[cfg(feature = "render")]
+use crate::components::plan_page::plan_page::PlanPage;
+#[cfg(feature = "render")]
+use crate::components::recent_plans::recent_plans::RecentPlans;
+#[cfg(feature = "render")]
+use crate::components::shared_plan::shared_plan::SharedPlan;
+#[cfg(feature = "render")]
+use crate::components::spell_editor::spell_editor::SpellEditor;
+#[cfg(feature = "render")]
 use crate::context::{use_class_spec_index, use_planner, UserContext, with_workers};
 #[cfg(feature = "render")]
 use crate::misc::localized_string_with_context::LocalizedStringWithContext;
@@ -78,6 +98,8 @@ pub fn App() -> impl IntoView {
         <Router>
             <Routes fallback>
                 <Route path=StaticSegment("") view=InnerApp/>
+                <Route path=StaticSegment("shared") view=SharedPlan/>
+                <Route path=(StaticSegment("plan"), ParamSegment("id")) view=PlanPage/>
             </Routes>
         </Router>
     }
@@ -103,6 +125,7 @@ pub fn InnerApp() -> impl IntoView {
 pub fn Planner() -> impl IntoView {
     let tab_open = RwSignal::new(false);
     use_optimizer();
+    use_undo_shortcuts();
     view! {
         <div class="flex h-full min-h-screen w-full select-none flex-col">
             <Header tab_open />
@@ -115,6 +138,7 @@ pub fn Planner() -> impl IntoView {
                 <CharacterSpellToggles/>
                 <CharacterHeaders/>
             </Main>
+            <DroppedAssignments/>
         </div>
     }
 }
@@ -143,9 +167,56 @@ pub fn Header(tab_open: RwSignal<bool>) -> impl IntoView {
                     </TabHeader>
                     <TabBody slot>
                         <NavTabBodyBackground image=boss_image />
+                        <DifficultyPicker />
+                        <RecentPlans />
                         //{move || planner.get().selected_fight().map(|f| f.parameters.run())}
                     </TabBody>
                 </Tab>
+                <Tab slot>
+                    <TabHeader slot>
+                        <div class="fa-solid fa-timeline mr-1"></div>
+                        <span>"Timeline"</span>
+                    </TabHeader>
+                    <TabBody slot>
+                        <CustomAttacks />
+                    </TabBody>
+                </Tab>
+                <Tab slot>
+                    <TabHeader slot>
+                        <div class="fa-solid fa-wand-magic-sparkles mr-1"></div>
+                        <span>"Spells"</span>
+                    </TabHeader>
+                    <TabBody slot>
+                        <SpellEditor />
+                    </TabBody>
+                </Tab>
+                <Tab slot>
+                    <TabHeader slot>
+                        <div class="fa-solid fa-file-import mr-1"></div>
+                        <span>"Import"</span>
+                    </TabHeader>
+                    <TabBody slot>
+                        <NoteImport />
+                    </TabBody>
+                </Tab>
+                <Tab slot>
+                    <TabHeader slot>
+                        <div class="fa-solid fa-file-export mr-1"></div>
+                        <span>"Export"</span>
+                    </TabHeader>
+                    <TabBody slot>
+                        <Export />
+                    </TabBody>
+                </Tab>
+                <Tab slot>
+                    <TabHeader slot>
+                        <div class="fa-solid fa-scroll mr-1"></div>
+                        <span>"Review"</span>
+                    </TabHeader>
+                    <TabBody slot>
+                        <LogReview />
+                    </TabBody>
+                </Tab>
                 <Tab slot>
                     <TabHeader slot>
                         <div class="fa-solid fa-gear mr-1"></div>
@@ -0,0 +1,80 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use convert_case::{Case, Casing};
+use leptos::prelude::*;
+use leptos::server_fn::codec::{GetUrl, Json};
+use tracing::instrument;
+
+use i18n::Region;
+
+/// Selected talent spell ids of the active loadout of each spec, by en_US spec name.
+#[instrument]
+#[server(prefix = "/bnet", input = GetUrl, output = Json)]
+pub async fn character_talents(
+    character_name: String,
+    realm_slug: String,
+    region: Region,
+    refresh: bool,
+) -> Result<HashMap<String, BTreeSet<u32>>, ServerFnError> {
+    use auto_battle_net::BattleNetClientAsync;
+    use auto_battle_net::profile::character_specializations::character_specializations_summary::CharacterSpecializationsSummaryRequest;
+    use i18n::Locale;
+
+    use super::util::{get_bnet_client, get_storage, ClientType};
+
+    async fn inner(
+        character_name: String,
+        realm_slug: String,
+        region: Region,
+    ) -> Result<HashMap<String, BTreeSet<u32>>, ServerFnError> {
+        let client = get_bnet_client(region, ClientType::AllowFallback).await?;
+        let summary = client
+            .call_async(CharacterSpecializationsSummaryRequest {
+                realm_slug,
+                character_name: character_name.to_case(Case::Kebab),
+            })
+            .await?;
+
+        let talents = summary
+            .specializations
+            .into_iter()
+            .filter_map(|s| {
+                let loadout = s.loadouts.into_iter().find(|l| l.is_active)?;
+                let talents = loadout
+                    .selected_class_talents
+                    .into_iter()
+                    .map(|t| t.tooltip.spell_tooltip.spell.id as u32)
+                    .chain(
+                        loadout
+                            .selected_spec_talents
+                            .into_iter()
+                            .map(|t| t.tooltip.spell_tooltip.spell.id as u32),
+                    )
+                    .collect();
+                // the planner's spec names, the id does not depend on the translation
+                let spec = planner::specs::spec_name(s.specialization.id as u64)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| {
+                        s.specialization.name.get(Locale::EnglishUnitedStates).to_string()
+                    });
+                Some((spec, talents))
+            })
+            .collect();
+        Ok(talents)
+    }
+
+    let storage = get_storage().await?;
+    let key = (character_name.clone(), realm_slug.clone(), region);
+    if refresh {
+        storage
+            .clear::<_, HashMap<String, BTreeSet<u32>>>(&key)
+            .await;
+    }
+    storage.try_fetch(
+        &key,
+        Duration::from_secs(60 * 60),
+        move || inner(character_name, realm_slug, region),
+    )
+    .await
+}
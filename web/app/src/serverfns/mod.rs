@@ -2,14 +2,17 @@ mod battle_net_login_url;
 mod character_avatar;
 mod character_main_image;
 mod character_summary;
+mod character_talents;
 mod classes_and_specs;
 mod encounter_info;
 mod guild_roster;
 mod instance_info;
 mod is_logged_in;
 mod main_character;
+mod plans;
 mod realms_for_character;
 mod region_realms;
+mod spell_names;
 mod raids;
 #[cfg(feature = "ssr")]
 pub mod util;
@@ -18,12 +21,15 @@ pub use battle_net_login_url::*;
 pub use character_avatar::*;
 pub use character_main_image::*;
 pub use character_summary::*;
+pub use character_talents::*;
 pub use classes_and_specs::*;
 pub use encounter_info::*;
 pub use guild_roster::*;
 pub use instance_info::*;
 pub use is_logged_in::*;
 pub use main_character::*;
+pub use plans::*;
 pub use realms_for_character::*;
 pub use region_realms::*;
+pub use spell_names::*;
 pub use raids::*;
\ No newline at end of file
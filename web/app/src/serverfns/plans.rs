@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+
+use planner::{Difficulty, SavedPlan};
+
+/// Why a plan could not be read or saved, shown to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Error)]
+pub enum PlanError {
+    #[error("Log in to save plans online")]
+    Unauthorized,
+    #[error("The plan does not exist or belongs to someone else")]
+    NotFound,
+}
+
+// server functions send their custom errors as the displayed text
+impl FromStr for PlanError {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [PlanError::Unauthorized, PlanError::NotFound]
+            .into_iter()
+            .find(|error| error.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// The text to show for a failed plan request.
+pub fn plan_error_message(error: &ServerFnError<PlanError>) -> String {
+    match error {
+        ServerFnError::WrappedServerError(error) => error.to_string(),
+        error => error.to_string(),
+    }
+}
+
+/// A plan saved on the server by the logged in user.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerPlanSummary {
+    pub id: String,
+    pub fight: String,
+    pub difficulty: Difficulty,
+    pub characters: usize,
+    /// Seconds since the epoch.
+    pub updated_at: u64,
+}
+
+#[instrument(skip(plan))]
+#[server(prefix = "/plans", input = Json, output = Json)]
+pub async fn create_plan(plan: SavedPlan) -> Result<String, ServerFnError<PlanError>> {
+    use crate::serverfns::util::get_storage;
+
+    let user_id = current_user_id().await?;
+    let storage = get_storage().await.map_err(server_error)?;
+    Ok(storage.create_plan(user_id, &to_value(&plan)).await)
+}
+
+#[instrument(skip(plan))]
+#[server(prefix = "/plans", input = Json, output = Json)]
+pub async fn update_plan(id: String, plan: SavedPlan) -> Result<(), ServerFnError<PlanError>> {
+    use crate::serverfns::util::get_storage;
+
+    let user_id = current_user_id().await?;
+    let storage = get_storage().await.map_err(server_error)?;
+    if storage.update_plan(&id, user_id, &to_value(&plan)).await {
+        Ok(())
+    } else {
+        Err(PlanError::NotFound.into())
+    }
+}
+
+/// Plans can be read by anyone with the id.
+#[instrument]
+#[server(prefix = "/plans", input = Json, output = Json)]
+pub async fn server_plan(id: String) -> Result<SavedPlan, ServerFnError<PlanError>> {
+    use crate::serverfns::util::get_storage;
+
+    let storage = get_storage().await.map_err(server_error)?;
+    let stored = storage
+        .plan::<serde_json::Value>(&id)
+        .await
+        .ok_or(PlanError::NotFound)?;
+    SavedPlan::from_json(&stored.plan.to_string())
+        .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+}
+
+#[instrument]
+#[server(prefix = "/plans", input = Json, output = Json)]
+pub async fn my_plans() -> Result<Vec<ServerPlanSummary>, ServerFnError<PlanError>> {
+    use crate::serverfns::util::get_storage;
+
+    let user_id = current_user_id().await?;
+    let storage = get_storage().await.map_err(server_error)?;
+    Ok(storage
+        .plans::<serde_json::Value>(user_id)
+        .await
+        .into_iter()
+        .filter_map(|stored| {
+            let plan = SavedPlan::from_json(&stored.plan.to_string()).ok()?;
+            Some(ServerPlanSummary {
+                id: stored.id,
+                fight: plan.fight.name,
+                difficulty: plan.fight.difficulty,
+                // without the general character
+                characters: plan.characters.len().saturating_sub(1),
+                updated_at: stored.updated_at,
+            })
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+async fn current_user_id() -> Result<u64, ServerFnError<PlanError>> {
+    use crate::serverfns::util::get_session;
+
+    get_session()
+        .await
+        .ok()
+        .and_then(|session| session.data().user.as_ref().map(|user| user.id))
+        .ok_or_else(|| PlanError::Unauthorized.into())
+}
+
+#[cfg(feature = "ssr")]
+fn server_error(error: ServerFnError) -> ServerFnError<PlanError> {
+    ServerFnError::ServerError(error.to_string())
+}
+
+// stored with their version, so old plans are migrated when they are read
+#[cfg(feature = "ssr")]
+fn to_value(plan: &SavedPlan) -> serde_json::Value {
+    serde_json::from_str(&plan.to_json()).unwrap()
+}
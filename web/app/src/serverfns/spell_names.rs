@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use tracing::instrument;
+
+use i18n::LocalizedString;
+
+/// Translated Battle.net names of the given spells, spells without a name are left out.
+#[instrument]
+#[server(prefix = "/bnet", input = Json, output = Json)]
+pub async fn spell_names(
+    spell_ids: Vec<u32>,
+) -> Result<HashMap<u32, LocalizedString>, ServerFnError> {
+    use auto_battle_net::BattleNetClientAsync;
+    use auto_battle_net::game_data::spell::spell::SpellRequest;
+
+    use super::util::{get_bnet_client_regionless, get_storage, ClientType};
+
+    async fn inner(spell_id: u32) -> Result<LocalizedString, ServerFnError> {
+        let client = get_bnet_client_regionless(ClientType::AllowFallback).await?;
+        let spell = client
+            .call_async(SpellRequest {
+                spell_id: spell_id as i64,
+            })
+            .await?;
+        Ok(spell.name)
+    }
+
+    let storage = get_storage().await?;
+    let mut names = HashMap::new();
+    for spell_id in spell_ids {
+        // spell names only change with patches
+        let name = storage
+            .try_fetch(&(spell_id,), Duration::from_secs(7 * 24 * 60 * 60), move || {
+                inner(spell_id)
+            })
+            .await;
+        match name {
+            Ok(name) => {
+                names.insert(spell_id, name);
+            }
+            Err(e) => tracing::warn!("Failed to fetch name of spell {spell_id}: {e}"),
+        }
+    }
+    Ok(names)
+}
@@ -102,6 +102,7 @@ async fn main() {
         .route("/bnet/*fn_name", post(handle_server_fns))
         .route("/bnet/*fn_name", get(handle_server_fns).layer(SetResponseHeaderLayer::appending(CACHE_CONTROL, HeaderValue::from_str(&format!("max-age={}", 60 * 60)).unwrap())))
         .route("/bnet/login-callback", any(battle_net_login_callback))
+        .route("/plans/*fn_name", post(handle_server_fns))
         .route("/spell_icon/:spell_id", get(spell_icon))
         .route("/icon/:icon", get(icon));
 
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS plans
+(
+    id         TEXT    NOT NULL PRIMARY KEY,
+    owner      INTEGER NOT NULL,
+    plan       TEXT    NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS plans_owner ON plans (owner, updated_at);
@@ -6,10 +6,12 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 pub use keyable::Keyable;
+pub use plans::StoredPlan;
 
 use crate::store::{Store, StoreKey};
 
 mod keyable;
+mod plans;
 mod store;
 
 #[cfg(feature = "axum")]
@@ -0,0 +1,132 @@
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{get_system_time, Storage};
+
+const PLAN_ID_LENGTH: usize = 8;
+
+/// A plan kept until its owner replaces it, unlike the cached values of the store.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredPlan<V> {
+    pub id: String,
+    pub owner: u64,
+    pub plan: V,
+    /// Seconds since the epoch.
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl Storage {
+    /// Stores a new plan and returns its short id.
+    pub async fn create_plan<V: Serialize>(&self, owner: u64, plan: &V) -> String {
+        let now = get_system_time();
+        let plan = serde_json::to_value(plan).unwrap();
+        loop {
+            let stored = StoredPlan {
+                id: plan_id(),
+                owner,
+                plan: plan.clone(),
+                created_at: now,
+                updated_at: now,
+            };
+            // ids are short, so they can collide
+            if self.store.insert_plan(&stored).await {
+                return stored.id;
+            }
+        }
+    }
+
+    /// Replaces the plan, returns false when it does not exist or belongs to someone else.
+    pub async fn update_plan<V: Serialize>(&self, id: &str, owner: u64, plan: &V) -> bool {
+        let plan = serde_json::to_value(plan).unwrap();
+        self.store
+            .update_plan(id, owner, plan, get_system_time())
+            .await
+    }
+
+    pub async fn plan<V: DeserializeOwned>(&self, id: &str) -> Option<StoredPlan<V>> {
+        self.store.get_plan(id).await.and_then(from_value)
+    }
+
+    /// Plans of the owner, most recently updated first.
+    pub async fn plans<V: DeserializeOwned>(&self, owner: u64) -> Vec<StoredPlan<V>> {
+        self.store
+            .plans_of(owner)
+            .await
+            .into_iter()
+            .filter_map(from_value)
+            .collect()
+    }
+}
+
+fn from_value<V: DeserializeOwned>(stored: StoredPlan<Value>) -> Option<StoredPlan<V>> {
+    Some(StoredPlan {
+        id: stored.id,
+        owner: stored.owner,
+        plan: serde_json::from_value(stored.plan).ok()?,
+        created_at: stored.created_at,
+        updated_at: stored.updated_at,
+    })
+}
+
+fn plan_id() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(PLAN_ID_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::sqlite::SqLiteConnection;
+    use crate::store::{ConnectionHolder, Store};
+    use crate::Storage;
+
+    const OWNER: u64 = 1;
+    const OTHER: u64 = 2;
+
+    async fn storage() -> Storage {
+        let connection = SqLiteConnection::new("sqlite::memory:").await.unwrap();
+        Storage::new(Store::new(&ConnectionHolder::new(connection)))
+    }
+
+    #[tokio::test]
+    async fn plan_round_trip() {
+        let storage = storage().await;
+
+        let id = storage.create_plan(OWNER, &json!({ "name": "Kazzara" })).await;
+        assert!(storage.update_plan(&id, OWNER, &json!({ "name": "Rashok" })).await);
+
+        let stored = storage.plan::<serde_json::Value>(&id).await.unwrap();
+        assert_eq!(stored.id, id);
+        assert_eq!(stored.owner, OWNER);
+        assert_eq!(stored.plan, json!({ "name": "Rashok" }));
+        assert_eq!(storage.plans::<serde_json::Value>(OWNER).await, vec![stored]);
+    }
+
+    #[tokio::test]
+    async fn plans_of_others_are_not_updated() {
+        let storage = storage().await;
+        let id = storage.create_plan(OWNER, &json!({ "name": "Kazzara" })).await;
+
+        assert!(!storage.update_plan(&id, OTHER, &json!({ "name": "Rashok" })).await);
+
+        let stored = storage.plan::<serde_json::Value>(&id).await.unwrap();
+        assert_eq!(stored.plan, json!({ "name": "Kazzara" }));
+        assert!(storage.plans::<serde_json::Value>(OTHER).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_plan_is_none() {
+        let storage = storage().await;
+
+        assert_eq!(storage.plan::<serde_json::Value>("unknown").await, None);
+        assert!(!storage.update_plan("unknown", OWNER, &json!({})).await);
+    }
+}
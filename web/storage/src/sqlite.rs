@@ -1,4 +1,5 @@
 use crate::get_system_time;
+use crate::plans::StoredPlan;
 use crate::store::{Connection, StoreKey};
 use async_trait::async_trait;
 use serde_json::Value;
@@ -129,6 +130,111 @@ impl Connection for SqLiteConnection {
         .await
         .unwrap();
     }
+
+    async fn insert_plan(&self, plan: &StoredPlan<Value>) -> bool {
+        let mut conn = self.pool.acquire().await.unwrap();
+        let owner = plan.owner as i64;
+        let value = serde_json::to_string(&plan.plan).unwrap();
+        let created_at = plan.created_at as i64;
+        let updated_at = plan.updated_at as i64;
+
+        sqlx::query!(
+            r#"INSERT INTO plans (
+                id,
+                owner,
+                plan,
+                created_at,
+                updated_at
+            ) VALUES($1, $2, $3, $4, $5)
+            ON CONFLICT(id) DO NOTHING
+            ;"#,
+            plan.id,
+            owner,
+            value,
+            created_at,
+            updated_at
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap()
+        .rows_affected()
+            == 1
+    }
+
+    async fn update_plan(&self, id: &str, owner: u64, plan: Value, updated_at: u64) -> bool {
+        let mut conn = self.pool.acquire().await.unwrap();
+        let owner = owner as i64;
+        let value = serde_json::to_string(&plan).unwrap();
+        let updated_at = updated_at as i64;
+
+        sqlx::query!(
+            r#"UPDATE plans
+            SET plan = $1,
+                updated_at = $2
+            WHERE id = $3
+            AND owner = $4
+            ;"#,
+            value,
+            updated_at,
+            id,
+            owner
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap()
+        .rows_affected()
+            == 1
+    }
+
+    async fn get_plan(&self, id: &str) -> Option<StoredPlan<Value>> {
+        let mut conn = self.pool.acquire().await.unwrap();
+
+        let row = sqlx::query!(
+            r#"SELECT id, owner, plan, created_at, updated_at
+            FROM plans
+            WHERE id = ?
+            ;"#,
+            id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .unwrap()?;
+        Some(StoredPlan {
+            id: row.id,
+            owner: row.owner as u64,
+            plan: serde_json::from_str(&row.plan).ok()?,
+            created_at: row.created_at as u64,
+            updated_at: row.updated_at as u64,
+        })
+    }
+
+    async fn plans_of(&self, owner: u64) -> Vec<StoredPlan<Value>> {
+        let mut conn = self.pool.acquire().await.unwrap();
+        let owner = owner as i64;
+
+        sqlx::query!(
+            r#"SELECT id, owner, plan, created_at, updated_at
+            FROM plans
+            WHERE owner = ?
+            ORDER BY updated_at DESC
+            ;"#,
+            owner
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|row| {
+            Some(StoredPlan {
+                id: row.id,
+                owner: row.owner as u64,
+                plan: serde_json::from_str(&row.plan).ok()?,
+                created_at: row.created_at as u64,
+                updated_at: row.updated_at as u64,
+            })
+        })
+        .collect()
+    }
 }
 
 fn hash<T: Hash>(t: &T) -> i64 {
@@ -12,6 +12,7 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::plans::StoredPlan;
 use crate::Keyable;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -72,6 +73,24 @@ impl Store {
         let _lock = self.locks.write(key.clone()).await;
         self.connection.delete(key).await
     }
+
+    pub async fn insert_plan(&self, plan: &StoredPlan<Value>) -> bool {
+        self.connection.insert_plan(plan).await
+    }
+
+    pub async fn update_plan(&self, id: &str, owner: u64, plan: Value, updated_at: u64) -> bool {
+        self.connection
+            .update_plan(id, owner, plan, updated_at)
+            .await
+    }
+
+    pub async fn get_plan(&self, id: &str) -> Option<StoredPlan<Value>> {
+        self.connection.get_plan(id).await
+    }
+
+    pub async fn plans_of(&self, owner: u64) -> Vec<StoredPlan<Value>> {
+        self.connection.plans_of(owner).await
+    }
 }
 
 #[derive(Clone)]
@@ -92,6 +111,12 @@ pub trait Connection: Send + Sync {
     async fn get(&self, key: &StoreKey) -> Option<Value>;
     async fn put(&self, key: &StoreKey, value: Value, ttl: Option<Duration>);
     async fn delete(&self, key: &StoreKey);
+    /// Returns false when the id is taken.
+    async fn insert_plan(&self, plan: &StoredPlan<Value>) -> bool;
+    /// Returns false when there is no plan with the id and owner.
+    async fn update_plan(&self, id: &str, owner: u64, plan: Value, updated_at: u64) -> bool;
+    async fn get_plan(&self, id: &str) -> Option<StoredPlan<Value>>;
+    async fn plans_of(&self, owner: u64) -> Vec<StoredPlan<Value>>;
 }
 
 pub(crate) trait ConnectionDyn: Send + Sync {
@@ -106,6 +131,25 @@ pub(crate) trait ConnectionDyn: Send + Sync {
         ttl: Option<&Duration>,
     ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
     fn delete<'a>(&'a self, key: &'a StoreKey) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+    fn insert_plan<'a>(
+        &'a self,
+        plan: &'a StoredPlan<Value>,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>>;
+    fn update_plan<'a>(
+        &'a self,
+        id: &'a str,
+        owner: u64,
+        plan: Value,
+        updated_at: u64,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>>;
+    fn get_plan<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<StoredPlan<Value>>> + Send + '_>>;
+    fn plans_of(
+        &self,
+        owner: u64,
+    ) -> Pin<Box<dyn Future<Output = Vec<StoredPlan<Value>>> + Send + '_>>;
 }
 
 impl<T: Connection> ConnectionDyn for T {
@@ -129,4 +173,37 @@ impl<T: Connection> ConnectionDyn for T {
     fn delete<'a>(&'a self, key: &'a StoreKey) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
         Box::pin(<Self as Connection>::delete(self, key))
     }
+
+    fn insert_plan<'a>(
+        &'a self,
+        plan: &'a StoredPlan<Value>,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(<Self as Connection>::insert_plan(self, plan))
+    }
+
+    fn update_plan<'a>(
+        &'a self,
+        id: &'a str,
+        owner: u64,
+        plan: Value,
+        updated_at: u64,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(<Self as Connection>::update_plan(
+            self, id, owner, plan, updated_at,
+        ))
+    }
+
+    fn get_plan<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<StoredPlan<Value>>> + Send + '_>> {
+        Box::pin(<Self as Connection>::get_plan(self, id))
+    }
+
+    fn plans_of(
+        &self,
+        owner: u64,
+    ) -> Pin<Box<dyn Future<Output = Vec<StoredPlan<Value>>> + Send + '_>> {
+        Box::pin(<Self as Connection>::plans_of(self, owner))
+    }
 }